@@ -0,0 +1,223 @@
+use crate::{
+    config::{number, value, Config},
+    solver::StrategyKind,
+};
+use std::{env, path::PathBuf};
+
+pub const DEFAULT_SCORE_FILE: &str = "guessing_game_scores.tsv";
+pub const DEFAULT_SOLVER_GAMES: u32 = 1000;
+pub const DEFAULT_TOLERANCE: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    Play(PlayOptions),
+    Scores {
+        score_file: PathBuf,
+    },
+    Replay {
+        transcript: PathBuf,
+    },
+    Solve {
+        config: Config,
+        strategies: Vec<StrategyKind>,
+        games: u32,
+    },
+    Reverse {
+        config: Config,
+    },
+    Variant {
+        config: Config,
+        variant: Variant,
+    },
+    Serve {
+        config: Config,
+        addr: String,
+    },
+    Connect {
+        addr: String,
+        player: String,
+    },
+}
+
+// The non-number secrets. They don't keep scores or transcripts, those are for the classic game.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Variant {
+    Letter,
+    Float { tolerance: f64 },
+    Word { dictionary: Option<PathBuf> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayOptions {
+    pub config: Config,
+    pub player: String,
+    pub score_file: PathBuf,
+    pub seed: Option<u64>,
+    pub record: Option<PathBuf>,
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    // the range and attempt flags are handed to `Config::from_args`
+    let mut config_args = Vec::new();
+    let mut player = None;
+    let mut score_file = None;
+    let mut show_scores = false;
+    let mut seed = None;
+    let mut record = None;
+    let mut replay = None;
+    let mut strategies = None;
+    let mut games = None;
+    let mut reverse = false;
+    let mut secret = None;
+    let mut tolerance = None;
+    let mut dictionary = None;
+    let mut serve = None;
+    let mut connect = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--difficulty" | "--min" | "--max" | "--max-attempts" => {
+                let raw = value(&arg, &mut args)?;
+                config_args.push(arg);
+                config_args.push(raw);
+            }
+            "--name" => player = Some(value(&arg, &mut args)?),
+            "--score-file" => score_file = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--scores" => show_scores = true,
+            "--seed" => {
+                let raw = value(&arg, &mut args)?;
+                seed = Some(
+                    raw.trim()
+                        .parse()
+                        .map_err(|_| format!("`--seed` expects a positive number, got `{raw}`"))?,
+                )
+            }
+            "--record" => record = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--replay" => replay = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--solve" => {
+                strategies = Some(match value(&arg, &mut args)?.as_str() {
+                    "all" => StrategyKind::ALL.to_vec(),
+                    name => vec![StrategyKind::parse(name)?],
+                })
+            }
+            "--games" => games = Some(number(&arg, &mut args)?),
+            "--reverse" => reverse = true,
+            "--secret" => secret = Some(value(&arg, &mut args)?),
+            "--tolerance" => {
+                let raw = value(&arg, &mut args)?;
+                tolerance = match raw.trim().parse::<f64>() {
+                    Ok(tolerance) if tolerance.is_finite() && tolerance >= 0.0 => Some(tolerance),
+                    _ => {
+                        return Err(format!(
+                            "`--tolerance` expects a positive number, got `{raw}`"
+                        ))
+                    }
+                }
+            }
+            "--dictionary" => dictionary = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--serve" => serve = Some(value(&arg, &mut args)?),
+            "--connect" => connect = Some(value(&arg, &mut args)?),
+            other => return Err(format!("unknown argument `{other}`")),
+        }
+    }
+
+    // each of these picks what the program does, so two of them would mean ignoring one
+    let modes = [
+        ("--scores", show_scores),
+        ("--replay", replay.is_some()),
+        ("--reverse", reverse),
+        ("--solve", strategies.is_some()),
+        ("--serve", serve.is_some()),
+        ("--connect", connect.is_some()),
+        (
+            "--secret",
+            secret.as_deref().is_some_and(|secret| secret != "number"),
+        ),
+    ];
+    let mut given = modes.iter().filter(|(_, given)| *given);
+    if let (Some((first, _)), Some((second, _))) = (given.next(), given.next()) {
+        return Err(format!("`{first}` and `{second}` can't be used together"));
+    }
+
+    let score_file = score_file.unwrap_or_else(|| PathBuf::from(DEFAULT_SCORE_FILE));
+    if show_scores {
+        return Ok(Command::Scores { score_file });
+    }
+    if let Some(transcript) = replay {
+        return Ok(Command::Replay { transcript });
+    }
+
+    let config = match Config::from_args(config_args)? {
+        Some(config) => config,
+        None => return Ok(Command::Help),
+    };
+
+    if reverse {
+        return Ok(Command::Reverse { config });
+    }
+    let variant = match secret.as_deref() {
+        None | Some("number") => None,
+        Some("letter") => Some(Variant::Letter),
+        Some("float") => Some(Variant::Float {
+            tolerance: tolerance.unwrap_or(DEFAULT_TOLERANCE),
+        }),
+        Some("word") => Some(Variant::Word { dictionary }),
+        Some(other) => {
+            return Err(format!(
+                "unknown secret `{other}`, expected number, letter, float or word"
+            ))
+        }
+    };
+    if let Some(variant) = variant {
+        // scores, transcripts and multiplayer are only for the classic number game
+        let number_only = [
+            ("--seed", seed.is_some()),
+            ("--record", record.is_some()),
+            ("--serve", serve.is_some()),
+        ];
+        if let Some((flag, _)) = number_only.iter().find(|(_, given)| *given) {
+            return Err(format!(
+                "`{flag}` only works with `--secret number`, not `--secret {}`",
+                secret.unwrap_or_default()
+            ));
+        }
+        return Ok(Command::Variant { config, variant });
+    }
+    if let Some(addr) = serve {
+        return Ok(Command::Serve { config, addr });
+    }
+    if let Some(strategies) = strategies {
+        return Ok(Command::Solve {
+            config,
+            strategies,
+            games: games.unwrap_or(DEFAULT_SOLVER_GAMES),
+        });
+    }
+    if games.is_some() {
+        return Err(String::from(
+            "`--games` only makes sense together with `--solve`",
+        ));
+    }
+
+    let player = player
+        .or_else(|| env::var("USER").ok())
+        .or_else(|| env::var("USERNAME").ok())
+        .unwrap_or_else(|| String::from("anonymous"));
+    if let Some(addr) = connect {
+        return Ok(Command::Connect { addr, player });
+    }
+
+    Ok(Command::Play(PlayOptions {
+        config,
+        player,
+        score_file,
+        seed,
+        record,
+    }))
+}
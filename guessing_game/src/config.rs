@@ -0,0 +1,166 @@
+use std::fmt;
+
+pub const USAGE: &str = "\
+Usage: guessing_game [OPTIONS]
+
+Options:
+    --difficulty <easy|normal|hard>  use a preset range and attempt budget
+    --min <N>                        smallest possible secret number (default 1)
+    --max <N>                        largest possible secret number (default 100)
+    --max-attempts <N>               number of guesses allowed (default unlimited)
+    --name <NAME>                    player name for the high-score table (default $USER)
+    --score-file <PATH>              where scores are kept (default guessing_game_scores.tsv)
+    --scores                         print the leaderboard instead of playing
+    --seed <N>                       pick the secret number from this seed
+    --record <PATH>                  write a transcript of the game to PATH
+    --replay <PATH>                  replay a recorded transcript and check it still matches
+    --solve <binary|random|linear|all>
+                                     let the computer play itself and report how it did
+    --games <N>                      number of games for --solve (default 1000)
+    --reverse                        you think of a number and the computer guesses it
+    --secret <number|letter|float|word>
+                                     what kind of secret to guess (default number)
+    --tolerance <T>                  how close a --secret float guess has to be (default 0.5)
+    --dictionary <PATH>              words for --secret word, one per line
+    --serve <ADDR>                   host a multiplayer game, e.g. --serve 127.0.0.1:7878
+    --connect <ADDR>                 join a multiplayer game as --name
+    -h, --help                       print this message";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    // every preset gives a bit more room than a perfect binary search would need
+    pub fn preset(self) -> (u32, u32, u32) {
+        match self {
+            Difficulty::Easy => (1, 50, 10),
+            Difficulty::Normal => (1, 100, 8),
+            Difficulty::Hard => (1, 1000, 11),
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Difficulty, String> {
+        match name.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            other => Err(format!(
+                "unknown difficulty `{other}`, expected easy, normal or hard"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub min: u32,
+    pub max: u32,
+    pub max_attempts: Option<u32>,
+    // `None` means the range or the attempt budget doesn't match any preset
+    pub difficulty: Option<Difficulty>,
+}
+
+impl Default for Config {
+    // the classic game from the book: 1 to 100 with as many guesses as you like
+    fn default() -> Config {
+        Config {
+            min: 1,
+            max: 100,
+            max_attempts: None,
+            difficulty: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_difficulty(difficulty: Difficulty) -> Config {
+        let (min, max, max_attempts) = difficulty.preset();
+        Config {
+            min,
+            max,
+            max_attempts: Some(max_attempts),
+            difficulty: Some(difficulty),
+        }
+    }
+
+    // Returns `Ok(None)` when the user asked for the help text.
+    pub fn from_args<I>(args: I) -> Result<Option<Config>, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        let mut difficulty = None;
+        let mut min = None;
+        let mut max = None;
+        let mut max_attempts = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--difficulty" => difficulty = Some(Difficulty::parse(&value(&arg, &mut args)?)?),
+                "--min" => min = Some(number(&arg, &mut args)?),
+                "--max" => max = Some(number(&arg, &mut args)?),
+                "--max-attempts" => max_attempts = Some(number(&arg, &mut args)?),
+                other => return Err(format!("unknown argument `{other}`")),
+            }
+        }
+
+        let mut config = match difficulty {
+            Some(difficulty) => Config::from_difficulty(difficulty),
+            None => Config::default(),
+        };
+        // explicit flags win over the preset, but then it's no longer that preset
+        if min.is_some() || max.is_some() || max_attempts.is_some() {
+            config.difficulty = None;
+        }
+        config.min = min.unwrap_or(config.min);
+        config.max = max.unwrap_or(config.max);
+        config.max_attempts = max_attempts.or(config.max_attempts);
+
+        config.validate()?;
+        Ok(Some(config))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min > self.max {
+            return Err(format!(
+                "--min ({}) must not be greater than --max ({})",
+                self.min, self.max
+            ));
+        }
+        if self.max_attempts == Some(0) {
+            return Err(String::from("--max-attempts must be at least 1"));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn value<I: Iterator<Item = String>>(
+    flag: &str,
+    args: &mut I,
+) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("`{flag}` expects a value"))
+}
+
+pub(crate) fn number<I: Iterator<Item = String>>(flag: &str, args: &mut I) -> Result<u32, String> {
+    let raw = value(flag, args)?;
+    raw.trim()
+        .parse()
+        .map_err(|_| format!("`{flag}` expects a positive number, got `{raw}`"))
+}
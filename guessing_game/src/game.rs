@@ -0,0 +1,409 @@
+use crate::{config::Config, secret::Secret};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    InProgress,
+    Won { attempts: u32 },
+    OutOfAttempts,
+    Quit,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::InProgress => write!(f, "still playing"),
+            Outcome::Won { attempts: 1 } => write!(f, "won in 1 attempt"),
+            Outcome::Won { attempts } => write!(f, "won in {attempts} attempts"),
+            Outcome::OutOfAttempts => write!(f, "out of attempts"),
+            Outcome::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+// Why a line of input wasn't accepted as a guess. None of these cost an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    Empty,
+    NotANumber(String),
+    Negative,
+    TooLarge,
+    OutOfRange { guess: u32, min: u32, max: u32 },
+    Repeated(String),
+    // anything a particular kind of secret rejects, with the message to show
+    Invalid(String),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::Empty => write!(f, "Please type a guess, or quit to give up."),
+            GuessError::NotANumber(input) => {
+                write!(
+                    f,
+                    "`{input}` is not a number, type a number or quit to give up."
+                )
+            }
+            GuessError::Negative => write!(f, "Negative numbers can't be the secret number."),
+            GuessError::TooLarge => write!(f, "That number is way too large to be the secret."),
+            GuessError::OutOfRange { guess, min, max } => {
+                write!(
+                    f,
+                    "{guess} is out of range, the number is between {min} and {max}."
+                )
+            }
+            GuessError::Repeated(guess) => {
+                write!(f, "You already guessed {guess}, try something different.")
+            }
+            GuessError::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+// How close a wrong guess was, relative to the size of the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Proximity {
+    Burning,
+    Hot,
+    Warm,
+    Cold,
+}
+
+impl fmt::Display for Proximity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hint = match self {
+            Proximity::Burning => "You're burning hot!",
+            Proximity::Hot => "You're hot.",
+            Proximity::Warm => "You're warm.",
+            Proximity::Cold => "You're cold.",
+        };
+        write!(f, "{hint}")
+    }
+}
+
+// The game itself doesn't know anything about stdin or stdout, it only keeps track of the
+// secret and the guesses made so far. `play` below wires it up to any reader/writer.
+// Without a type parameter it's the classic number game.
+#[derive(Debug, Clone)]
+pub struct Game<S: Secret = u32> {
+    config: Config,
+    secret: S,
+    attempts: u32,
+    previous_guesses: Vec<S::Guess>,
+    outcome: Outcome,
+}
+
+impl Game {
+    // Taking the rng as a parameter lets tests pass a seeded one instead of `thread_rng()`
+    pub fn new<R: Rng + ?Sized>(config: Config, rng: &mut R) -> Game {
+        let secret_number = rng.gen_range(config.min..=config.max);
+        Game::with_secret(config, secret_number)
+    }
+
+    // The same seed and config always give the same secret number, which is what makes
+    // recorded games replayable. ChaCha8 is used by name because `StdRng` may change what it
+    // generates between rand releases, and old transcripts have to keep replaying.
+    pub fn from_seed(config: Config, seed: u64) -> Game {
+        Game::new(config, &mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    pub fn with_secret(config: Config, secret_number: u32) -> Game {
+        Game::from_secret(config, secret_number)
+    }
+
+    pub fn secret_number(&self) -> u32 {
+        self.secret
+    }
+}
+
+impl<S: Secret> Game<S> {
+    pub fn from_secret(config: Config, secret: S) -> Game<S> {
+        Game {
+            config,
+            secret,
+            attempts: 0,
+            previous_guesses: Vec::new(),
+            outcome: Outcome::InProgress,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn secret(&self) -> &S {
+        &self.secret
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn previous_guesses(&self) -> &[S::Guess] {
+        &self.previous_guesses
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.config
+            .max_attempts
+            .map(|max_attempts| max_attempts.saturating_sub(self.attempts))
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome != Outcome::InProgress
+    }
+
+    // Returns how the guess compares to the secret, or `None` if the game is already over and
+    // the guess was ignored.
+    pub fn guess(&mut self, guess: S::Guess) -> Option<Ordering> {
+        if self.is_over() {
+            return None;
+        }
+        self.attempts += 1;
+
+        let ordering = self.secret.compare(&guess);
+        self.previous_guesses.push(guess);
+        if ordering == Ordering::Equal {
+            self.outcome = Outcome::Won {
+                attempts: self.attempts,
+            };
+        } else if self.attempts_left() == Some(0) {
+            self.outcome = Outcome::OutOfAttempts;
+        }
+        Some(ordering)
+    }
+
+    // Turns a line of input into a guess, rejecting anything that shouldn't cost an attempt.
+    pub fn parse_guess(&self, input: &str) -> Result<S::Guess, GuessError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let guess = self.secret.parse_guess(input, &self.config)?;
+        if self.previous_guesses.contains(&guess) {
+            return Err(GuessError::Repeated(guess.to_string()));
+        }
+        Ok(guess)
+    }
+
+    // Not every kind of secret has a sense of distance, words for example don't.
+    pub fn proximity(&self, guess: &S::Guess) -> Option<Proximity> {
+        self.secret.proximity(guess, &self.config)
+    }
+
+    pub fn quit(&mut self) {
+        if !self.is_over() {
+            self.outcome = Outcome::Quit;
+        }
+    }
+}
+
+// Runs the game loop until it's won, lost or quit. Reaching the end of `input` counts as quitting.
+pub fn play<S, R, W>(game: &mut Game<S>, input: R, output: W) -> io::Result<Outcome>
+where
+    S: Secret,
+    R: BufRead,
+    W: Write,
+{
+    play_turns(game, input, output, |_, _| {})
+}
+
+// Same as `play`, but hands every line read and the responses to it over to `on_turn`, which
+// is what recording a transcript hooks into.
+pub fn play_turns<S, R, W, F>(
+    game: &mut Game<S>,
+    mut input: R,
+    mut output: W,
+    mut on_turn: F,
+) -> io::Result<Outcome>
+where
+    S: Secret,
+    R: BufRead,
+    W: Write,
+    F: FnMut(&str, &[String]),
+{
+    let config = game.config().clone();
+    writeln!(output, "Guess the {}!", game.secret().noun())?;
+    if let Some(difficulty) = config.difficulty {
+        writeln!(output, "Difficulty: {difficulty}.")?;
+    }
+    writeln!(output, "{}", game.secret().describe(&config))?;
+    match config.max_attempts {
+        Some(1) => writeln!(output, "You have 1 attempt.")?,
+        Some(max_attempts) => writeln!(output, "You have {max_attempts} attempts.")?,
+        None => {}
+    }
+
+    while !game.is_over() {
+        writeln!(output, "Please input your guess.")?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            game.quit();
+            break;
+        }
+
+        let line = line.trim();
+        let responses = respond(game, line);
+        for response in &responses {
+            writeln!(output, "{response}")?;
+        }
+        on_turn(line, &responses);
+    }
+
+    Ok(game.outcome())
+}
+
+// Everything the game says back to one line of input.
+pub fn respond<S: Secret>(game: &mut Game<S>, line: &str) -> Vec<String> {
+    let mut responses = Vec::new();
+    if line.trim().eq("quit") {
+        game.quit();
+        return responses;
+    }
+
+    let guess = match game.parse_guess(line) {
+        Ok(guess) => guess,
+        Err(error) => {
+            responses.push(error.to_string());
+            return responses;
+        }
+    };
+
+    let proximity = game.proximity(&guess);
+    let response = match game.guess(guess) {
+        Some(Ordering::Less) => "Too small!",
+        Some(Ordering::Greater) => "Too big!",
+        Some(Ordering::Equal) => "You win!",
+        None => return responses,
+    };
+    responses.push(response.to_string());
+
+    match game.outcome() {
+        Outcome::OutOfAttempts => responses.push(format!(
+            "Out of attempts, the {} was {}.",
+            game.secret().noun(),
+            game.secret().reveal()
+        )),
+        Outcome::InProgress => {
+            if let Some(proximity) = proximity {
+                responses.push(proximity.to_string());
+            }
+            match game.attempts_left() {
+                Some(1) => responses.push(String::from("1 attempt left.")),
+                Some(left) => responses.push(format!("{left} attempts left.")),
+                None => {}
+            }
+        }
+        _ => {}
+    }
+    responses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(game: &mut Game, input: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome = play(game, input.as_bytes(), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn seeded_rng_picks_the_same_secret() {
+        let first = Game::new(Config::default(), &mut ChaCha8Rng::seed_from_u64(7));
+        let second = Game::new(Config::default(), &mut ChaCha8Rng::seed_from_u64(7));
+        assert_eq!(first.secret_number(), second.secret_number());
+        assert!((1..=100).contains(&first.secret_number()));
+        // recorded transcripts depend on this never changing
+        assert_eq!(Game::from_seed(Config::default(), 1234).secret_number(), 71);
+    }
+
+    #[test]
+    fn outcomes_read_naturally() {
+        assert_eq!(Outcome::InProgress.to_string(), "still playing");
+        assert_eq!(Outcome::Won { attempts: 1 }.to_string(), "won in 1 attempt");
+        assert_eq!(
+            Outcome::Won { attempts: 4 }.to_string(),
+            "won in 4 attempts"
+        );
+        assert_eq!(Outcome::OutOfAttempts.to_string(), "out of attempts");
+        assert_eq!(Outcome::Quit.to_string(), "quit");
+    }
+
+    #[test]
+    fn too_small_too_big_then_win() {
+        let mut game = Game::with_secret(Config::default(), 42);
+        let (outcome, output) = run(&mut game, "10\n90\n42\n");
+
+        assert_eq!(outcome, Outcome::Won { attempts: 3 });
+        let responses: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("Too") || line.starts_with("You win"))
+            .collect();
+        assert_eq!(responses, ["Too small!", "Too big!", "You win!"]);
+    }
+
+    #[test]
+    fn quit_and_end_of_input_stop_the_game() {
+        let mut game = Game::with_secret(Config::default(), 42);
+        assert_eq!(run(&mut game, "10\nquit\n42\n").0, Outcome::Quit);
+        assert_eq!(game.attempts(), 1);
+
+        let mut game = Game::with_secret(Config::default(), 42);
+        assert_eq!(run(&mut game, "").0, Outcome::Quit);
+    }
+
+    #[test]
+    fn invalid_guesses_explain_themselves_without_costing_attempts() {
+        let mut game = Game::with_secret(Config::default(), 42);
+        let (outcome, output) = run(&mut game, "abc\n\n-5\n99999999999\n0\n500\n10\n10\n42\n");
+
+        assert_eq!(outcome, Outcome::Won { attempts: 2 });
+        for message in [
+            "`abc` is not a number",
+            "Please type a guess",
+            "Negative numbers",
+            "way too large",
+            "0 is out of range, the number is between 1 and 100.",
+            "500 is out of range",
+            "You already guessed 10",
+        ] {
+            assert!(output.contains(message), "missing {message:?} in {output}");
+        }
+    }
+
+    #[test]
+    fn proximity_hints_scale_with_the_range() {
+        let game = Game::with_secret(Config::default(), 50);
+        assert_eq!(game.proximity(&51), Some(Proximity::Burning));
+        assert_eq!(game.proximity(&58), Some(Proximity::Hot));
+        assert_eq!(game.proximity(&70), Some(Proximity::Warm));
+        assert_eq!(game.proximity(&1), Some(Proximity::Cold));
+    }
+
+    #[test]
+    fn running_out_of_attempts_reveals_the_number() {
+        let config = Config {
+            max_attempts: Some(2),
+            ..Config::default()
+        };
+        let mut game = Game::with_secret(config, 42);
+        let (outcome, output) = run(&mut game, "1\n2\n42\n");
+
+        assert_eq!(outcome, Outcome::OutOfAttempts);
+        assert!(output.contains("1 attempt left."));
+        assert!(output.ends_with("Out of attempts, the number was 42.\n"));
+        assert_eq!(game.guess(42), None);
+    }
+}
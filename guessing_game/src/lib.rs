@@ -0,0 +1,15 @@
+pub mod cli;
+pub mod config;
+pub mod game;
+pub mod net;
+pub mod reverse;
+pub mod scores;
+pub mod secret;
+pub mod solver;
+pub mod transcript;
+
+pub use config::{Config, Difficulty};
+pub use game::{play, play_turns, Game, GuessError, Outcome, Proximity};
+pub use scores::{Score, ScoreBoard};
+pub use secret::{Float, Letter, Secret, Word};
+pub use transcript::Transcript;
@@ -1,35 +1,225 @@
+use guessing_game::{
+    cli::{self, Command, PlayOptions, Variant},
+    config::USAGE,
+    net::{self, Server},
+    play, play_turns,
+    reverse::{play_reverse, ReverseGame},
+    secret::{load_dictionary, DEFAULT_WORDS},
+    solver::{self, BinarySearch, StrategyKind},
+    Config, Float, Game, Letter, Outcome, Score, ScoreBoard, Secret, Transcript, Word,
+};
 use rand::Rng;
-use std::{cmp::Ordering, io};
+use std::{
+    cmp::Ordering,
+    env, fs,
+    io::{self, BufReader},
+    process,
+    time::Instant,
+};
 
 fn main() {
-    println!("Guess the number!");
-    let secret_number = rand::thread_rng().gen_range(1..=100);
-
-    loop {
-        println!("Please input your guess.");
-        let mut guess = String::new();
-
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                if guess.trim().eq("quit") {
-                    break;
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Help => println!("{USAGE}"),
+        Command::Scores { score_file } => {
+            let board = ScoreBoard::load(&score_file).unwrap_or_else(|error| {
+                eprintln!("error: couldn't read {}: {error}", score_file.display());
+                process::exit(1);
+            });
+            board
+                .print(io::stdout().lock())
+                .expect("Failed to print the scores");
+        }
+        Command::Solve {
+            config,
+            strategies,
+            games,
+        } => solve(config, strategies, games),
+        Command::Reverse { config } => {
+            let mut game = ReverseGame::new(config, Box::new(BinarySearch));
+            play_reverse(&mut game, io::stdin().lock(), io::stdout().lock())
+                .expect("Failed to play the game");
+        }
+        Command::Variant { config, variant } => play_variant(config, variant),
+        Command::Replay { transcript } => {
+            let transcript = Transcript::load(&transcript).unwrap_or_else(|message| {
+                eprintln!("error: {message}");
+                process::exit(2);
+            });
+            let mismatches = transcript
+                .replay(io::stdout().lock())
+                .expect("Failed to replay the game");
+            if mismatches.is_empty() {
+                println!(
+                    "All {} turns matched the recording.",
+                    transcript.turns.len()
+                );
+            } else {
+                println!(
+                    "{} of {} turns differ from the recording.",
+                    mismatches.len(),
+                    transcript.turns.len()
+                );
+                process::exit(1);
+            }
+        }
+        Command::Serve { config, addr } => serve(config, &addr),
+        Command::Connect { addr, player } => {
+            if let Err(error) =
+                net::connect(&*addr, &player, BufReader::new(io::stdin()), io::stdout())
+            {
+                eprintln!("error: couldn't play on {addr}: {error}");
+                process::exit(1);
+            }
+        }
+        Command::Play(options) => play_game(options),
+    }
+}
+
+fn play_variant(config: Config, variant: Variant) {
+    let mut rng = rand::thread_rng();
+    match variant {
+        Variant::Letter => play_secret(Game::from_secret(config, Letter::random(&mut rng))),
+        Variant::Float { tolerance } => {
+            let secret = Float::random(&config, tolerance, &mut rng);
+            play_secret(Game::from_secret(config, secret))
+        }
+        Variant::Word { dictionary } => {
+            let words: Vec<String> = match dictionary {
+                Some(path) => match fs::read_to_string(&path) {
+                    Ok(text) => load_dictionary(&text),
+                    Err(error) => {
+                        eprintln!("error: couldn't read {}: {error}", path.display());
+                        process::exit(1);
+                    }
+                },
+                None => DEFAULT_WORDS.iter().map(|word| word.to_string()).collect(),
+            };
+            match Word::random(&words, &mut rng) {
+                Some(secret) => play_secret(Game::from_secret(config, secret)),
+                None => {
+                    eprintln!("error: the dictionary doesn't have any words made only of letters");
+                    process::exit(1);
                 }
-                continue;
             }
-        };
-
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
+        }
+    }
+}
+
+fn play_secret<S: Secret>(mut game: Game<S>) {
+    play(&mut game, io::stdin().lock(), io::stdout().lock()).expect("Failed to play the game");
+}
+
+fn serve(config: Config, addr: &str) {
+    let game = Game::new(config, &mut rand::thread_rng());
+    let result = Server::bind(addr, game).and_then(|server| {
+        println!(
+            "Hosting a game on {}, waiting for players...",
+            server.local_addr()?
+        );
+        server.run()
+    });
+    match result {
+        Ok(Some(winner)) => println!("{winner} won the game."),
+        Ok(None) => println!("Nobody won this time."),
+        Err(error) => {
+            eprintln!("error: couldn't host a game on {addr}: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn solve(config: Config, strategies: Vec<StrategyKind>, games: u32) {
+    let mut rng = rand::thread_rng();
+
+    // a single game is more interesting to watch guess by guess
+    if games == 1 {
+        for kind in strategies {
+            let mut game = Game::new(config.clone(), &mut rng);
+            println!("{kind}, secret number {}:", game.secret_number());
+            for (guess, ordering) in solver::solve(&mut game, kind.build().as_mut()) {
+                let feedback = match ordering {
+                    Ordering::Less => "too small",
+                    Ordering::Greater => "too big",
+                    Ordering::Equal => "correct",
+                };
+                println!("  guessed {guess:>10}: {feedback}");
             }
+            match game.outcome() {
+                Outcome::Won { attempts } => println!("  won in {attempts} attempts"),
+                _ => println!("  out of attempts after {}", game.attempts()),
+            }
+        }
+        return;
+    }
+
+    let results: Vec<_> = strategies
+        .into_iter()
+        .map(|kind| {
+            let result = solver::benchmark(&config, kind.build().as_mut(), games, &mut rng);
+            (kind, result)
+        })
+        .collect();
+    solver::print_report(io::stdout().lock(), &config, &results)
+        .expect("Failed to print the report");
+}
+
+fn play_game(options: PlayOptions) {
+    let PlayOptions {
+        config,
+        player,
+        score_file,
+        seed,
+        record,
+    } = options;
+
+    // even without --seed the game runs from a seed, so it can always be recorded
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut game = Game::from_seed(config.clone(), seed);
+    let mut transcript = Transcript::new(seed, config);
+    let started = Instant::now();
+    let outcome = play_turns(
+        &mut game,
+        io::stdin().lock(),
+        io::stdout().lock(),
+        |line, responses| transcript.push(line, responses),
+    )
+    .expect("Failed to play the game");
+
+    if let Some(path) = record {
+        match transcript.save(&path) {
+            Ok(()) => println!("Transcript saved to {}.", path.display()),
+            Err(error) => eprintln!(
+                "warning: couldn't save the transcript to {}: {error}",
+                path.display()
+            ),
         }
     }
+
+    let won = match outcome {
+        Outcome::Won { .. } => true,
+        Outcome::OutOfAttempts => false,
+        // quitting doesn't count as a finished game
+        Outcome::Quit | Outcome::InProgress => return,
+    };
+    let score = Score::new(
+        &player,
+        game.config(),
+        won,
+        game.attempts(),
+        started.elapsed(),
+    );
+    if let Err(error) = ScoreBoard::append(&score_file, &score) {
+        eprintln!(
+            "warning: couldn't save your score to {}: {error}",
+            score_file.display()
+        );
+    }
 }
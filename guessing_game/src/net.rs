@@ -0,0 +1,370 @@
+use crate::game::{Game, Outcome};
+use std::{
+    cmp::Ordering,
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+// How often the accept loop checks whether somebody already won.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+// A client that stops reading can't hold up everybody else's messages for longer than this.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The protocol is plain lines of text both ways: a client's first line is its name and every
+// line after that is a guess, and the server answers with lines meant to be printed as-is.
+pub struct Server {
+    listener: TcpListener,
+    game: Game,
+}
+
+struct Player {
+    stream: TcpStream,
+    playing: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    // every accepted connection, including ones that never sent a name, so that all of them
+    // can be hung up on when the game ends
+    connections: Vec<TcpStream>,
+    players: Vec<Player>,
+    finished: bool,
+    winner: Option<String>,
+}
+
+impl Shared {
+    // Copies of the players' streams, to write to once the lock is released.
+    fn recipients(&self) -> Vec<TcpStream> {
+        self.players
+            .iter()
+            .filter_map(|player| player.stream.try_clone().ok())
+            .collect()
+    }
+
+    // Ends the game and hands back every connection, to hang up on once the last messages
+    // have gone out.
+    fn finish(&mut self) -> Vec<TcpStream> {
+        self.finished = true;
+        self.connections
+            .iter()
+            .filter_map(|stream| stream.try_clone().ok())
+            .collect()
+    }
+}
+
+fn broadcast(recipients: &mut [TcpStream], message: &str) {
+    // a client that went away just misses the message
+    for stream in recipients {
+        let _ = writeln!(stream, "{message}");
+    }
+}
+
+fn hang_up(connections: &[TcpStream]) {
+    for stream in connections {
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
+
+impl Server {
+    // Every client plays its own copy of `game`, so they all race for the same secret number
+    // while keeping their own attempt counts.
+    pub fn bind<A: ToSocketAddrs>(addr: A, game: Game) -> io::Result<Server> {
+        let listener = TcpListener::bind(addr)?;
+        Ok(Server { listener, game })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // Accepts players until the game is over and returns the winner's name, if anybody won.
+    pub fn run(self) -> io::Result<Option<String>> {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let mut handles = Vec::new();
+        self.listener.set_nonblocking(true)?;
+
+        while !shared.lock().unwrap().finished {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(ACCEPT_POLL_INTERVAL);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            stream.set_nonblocking(false)?;
+            stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+            {
+                let mut shared = shared.lock().unwrap();
+                // the game ended while this one was being accepted
+                if shared.finished {
+                    let _ = stream.shutdown(Shutdown::Both);
+                    break;
+                }
+                shared.connections.push(stream.try_clone()?);
+            }
+
+            let shared = Arc::clone(&shared);
+            let game = self.game.clone();
+            handles.push(thread::spawn(move || handle_client(stream, game, shared)));
+        }
+
+        for handle in handles {
+            // a client thread only fails on I/O errors with that one client
+            let _ = handle.join();
+        }
+        let winner = shared.lock().unwrap().winner.clone();
+        Ok(winner)
+    }
+}
+
+fn handle_client(stream: TcpStream, game: Game, shared: Arc<Mutex<Shared>>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut name = String::new();
+    if reader.read_line(&mut name)? == 0 {
+        return Ok(());
+    }
+    let name = match name.trim() {
+        "" => String::from("anonymous"),
+        name => name.to_string(),
+    };
+
+    let (index, mut others) = {
+        let mut shared = shared.lock().unwrap();
+        if shared.finished {
+            return Ok(());
+        }
+        let others = shared.recipients();
+        shared.players.push(Player {
+            stream: stream.try_clone()?,
+            playing: true,
+        });
+        (shared.players.len() - 1, others)
+    };
+    broadcast(&mut others, &format!("{name} joined the game."));
+
+    let result = play(stream, reader, game, &name, index, &shared);
+    // however the player stopped, a dropped connection included, they're not playing anymore
+    leave(&shared, index, &name);
+    result
+}
+
+fn play(
+    mut stream: TcpStream,
+    mut reader: BufReader<TcpStream>,
+    mut game: Game,
+    name: &str,
+    index: usize,
+    shared: &Mutex<Shared>,
+) -> io::Result<()> {
+    let config = game.config().clone();
+    writeln!(
+        stream,
+        "Welcome {name}! Guess the number between {} and {}.",
+        config.min, config.max
+    )?;
+    if let Some(max_attempts) = config.max_attempts {
+        writeln!(stream, "You have {max_attempts} attempts.")?;
+    }
+
+    let mut line = String::new();
+    loop {
+        line.clear();
+        // the server shutting the socket down after a win also ends up here
+        if reader.read_line(&mut line)? == 0 || line.trim() == "quit" {
+            break;
+        }
+        if shared.lock().unwrap().finished {
+            break;
+        }
+        let guess = match game.parse_guess(&line) {
+            Ok(guess) => guess,
+            Err(error) => {
+                writeln!(stream, "{error}")?;
+                continue;
+            }
+        };
+
+        let response = match game.guess(guess) {
+            Some(Ordering::Less) => "Too small!",
+            Some(Ordering::Greater) => "Too big!",
+            Some(Ordering::Equal) => "Correct!",
+            None => break,
+        };
+        let mut messages = vec![format!("{name} guessed {guess}: {response}")];
+
+        let (mut recipients, connections) = {
+            let mut shared = shared.lock().unwrap();
+            // somebody else won in the meantime
+            if shared.finished {
+                break;
+            }
+            let mut connections = Vec::new();
+            match game.outcome() {
+                Outcome::Won { attempts } => {
+                    let attempts = match attempts {
+                        1 => String::from("1 attempt"),
+                        attempts => format!("{attempts} attempts"),
+                    };
+                    messages.push(format!(
+                        "{name} wins in {attempts}! The number was {}.",
+                        game.secret_number()
+                    ));
+                    shared.winner = Some(name.to_string());
+                    connections = shared.finish();
+                }
+                Outcome::OutOfAttempts => {
+                    messages.push(format!("{name} is out of attempts."));
+                    shared.players[index].playing = false;
+                    if shared.players.iter().all(|player| !player.playing) {
+                        messages.push(format!(
+                            "Nobody guessed it, the number was {}.",
+                            game.secret_number()
+                        ));
+                        connections = shared.finish();
+                    }
+                }
+                Outcome::InProgress | Outcome::Quit => {}
+            }
+            (shared.recipients(), connections)
+        };
+        for message in &messages {
+            broadcast(&mut recipients, message);
+        }
+        hang_up(&connections);
+
+        match game.outcome() {
+            Outcome::InProgress => match game.attempts_left() {
+                Some(1) => writeln!(stream, "1 attempt left.")?,
+                Some(left) => writeln!(stream, "{left} attempts left.")?,
+                None => {}
+            },
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+// Takes the player out of the game, which is over once nobody is left playing.
+fn leave(shared: &Mutex<Shared>, index: usize, name: &str) {
+    let (mut recipients, connections) = {
+        let mut shared = shared.lock().unwrap();
+        if shared.finished || !shared.players[index].playing {
+            return;
+        }
+        shared.players[index].playing = false;
+        let connections = if shared.players.iter().all(|player| !player.playing) {
+            shared.finish()
+        } else {
+            Vec::new()
+        };
+        (shared.recipients(), connections)
+    };
+    broadcast(&mut recipients, &format!("{name} left the game."));
+    hang_up(&connections);
+}
+
+// Sends the player's name and then every line of `input` to the server, printing whatever the
+// server sends back to `output` until it closes the connection.
+pub fn connect<A, R, W>(addr: A, name: &str, input: R, mut output: W) -> io::Result<()>
+where
+    A: ToSocketAddrs,
+    R: BufRead + Send + 'static,
+    W: Write,
+{
+    let stream = TcpStream::connect(addr)?;
+    let mut writer = stream.try_clone()?;
+    writeln!(writer, "{name}")?;
+
+    // reading the keyboard blocks, so it gets its own thread and is simply abandoned once the
+    // server hangs up
+    thread::spawn(move || -> io::Result<()> {
+        for line in input.lines() {
+            writeln!(writer, "{}", line?)?;
+        }
+        writer.shutdown(Shutdown::Write)
+    });
+
+    for line in BufReader::new(stream).lines() {
+        writeln!(output, "{}", line?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn read_until(reader: &mut impl BufRead, needle: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        for line in reader.lines() {
+            let line = line.unwrap();
+            let found = line.contains(needle);
+            lines.push(line);
+            if found {
+                break;
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn players_race_for_the_same_number_on_localhost() {
+        let server = Server::bind("127.0.0.1:0", Game::with_secret(Config::default(), 42)).unwrap();
+        let addr = server.local_addr().unwrap();
+        let server = thread::spawn(move || server.run());
+
+        let mut alice = TcpStream::connect(addr).unwrap();
+        let mut alice_lines = BufReader::new(alice.try_clone().unwrap());
+        writeln!(alice, "alice").unwrap();
+        read_until(&mut alice_lines, "Welcome alice!");
+
+        let mut bob = TcpStream::connect(addr).unwrap();
+        let mut bob_lines = BufReader::new(bob.try_clone().unwrap());
+        writeln!(bob, "bob").unwrap();
+        read_until(&mut bob_lines, "Welcome bob!");
+        read_until(&mut alice_lines, "bob joined the game.");
+
+        writeln!(alice, "abc").unwrap();
+        assert!(read_until(&mut alice_lines, "not a number")
+            .last()
+            .unwrap()
+            .contains("`abc`"));
+        writeln!(alice, "10").unwrap();
+        read_until(&mut bob_lines, "alice guessed 10: Too small!");
+        writeln!(bob, "42").unwrap();
+
+        let lines = read_until(&mut alice_lines, "The number was 42.");
+        assert_eq!(
+            lines.last().unwrap(),
+            "bob wins in 1 attempt! The number was 42."
+        );
+        assert_eq!(server.join().unwrap().unwrap(), Some(String::from("bob")));
+    }
+
+    #[test]
+    fn the_game_ends_when_the_last_player_leaves_even_with_silent_clients() {
+        let server = Server::bind("127.0.0.1:0", Game::with_secret(Config::default(), 42)).unwrap();
+        let addr = server.local_addr().unwrap();
+        let server = thread::spawn(move || server.run());
+
+        // connects but never says who it is
+        let silent = TcpStream::connect(addr).unwrap();
+
+        let mut alice = TcpStream::connect(addr).unwrap();
+        let mut alice_lines = BufReader::new(alice.try_clone().unwrap());
+        writeln!(alice, "alice").unwrap();
+        read_until(&mut alice_lines, "Welcome alice!");
+        drop(alice_lines);
+        drop(alice);
+
+        assert_eq!(server.join().unwrap().unwrap(), None);
+        // the server hung up on the silent client too
+        let mut rest = String::new();
+        assert_eq!(BufReader::new(silent).read_line(&mut rest).unwrap(), 0);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{config::Config, solver::Strategy};
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+// What the human answers about the program's guess, from the point of view of their number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl Answer {
+    pub fn parse(input: &str) -> Option<Answer> {
+        match input.trim().to_lowercase().as_str() {
+            "h" | "higher" | "+" => Some(Answer::Higher),
+            "l" | "lower" | "-" => Some(Answer::Lower),
+            "c" | "correct" | "=" | "yes" => Some(Answer::Correct),
+            _ => None,
+        }
+    }
+}
+
+// The answers so far leave no number that fits all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction {
+    // the largest guess the number was said to be higher than, if any
+    pub above: Option<u32>,
+    // the smallest guess the number was said to be lower than, if any
+    pub below: Option<u32>,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.above, self.below) {
+            (Some(above), Some(below)) => write!(
+                f,
+                "you said your number is higher than {above} and lower than {below}, \
+                 but there's no whole number in between"
+            ),
+            (Some(above), None) => write!(
+                f,
+                "you said your number is higher than {above}, but it can't be bigger than {}",
+                self.max
+            ),
+            (None, Some(below)) => write!(
+                f,
+                "you said your number is lower than {below}, but it can't be smaller than {}",
+                self.min
+            ),
+            (None, None) => write!(f, "there's no number left to guess"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReverseOutcome {
+    Found { number: u32, attempts: u32 },
+    Contradiction(Contradiction),
+    Quit,
+}
+
+// The program is the one guessing here: it keeps the range the human's number can still be
+// in and shrinks it with every answer.
+pub struct ReverseGame {
+    config: Config,
+    strategy: Box<dyn Strategy>,
+    low: u32,
+    high: u32,
+    above: Option<u32>,
+    below: Option<u32>,
+    current_guess: Option<u32>,
+    attempts: u32,
+}
+
+impl ReverseGame {
+    pub fn new(config: Config, strategy: Box<dyn Strategy>) -> ReverseGame {
+        ReverseGame {
+            low: config.min,
+            high: config.max,
+            config,
+            strategy,
+            above: None,
+            below: None,
+            current_guess: None,
+            attempts: 0,
+        }
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    // Asking again without answering repeats the same guess.
+    pub fn next_guess(&mut self) -> u32 {
+        if let Some(guess) = self.current_guess {
+            return guess;
+        }
+        let guess = self
+            .strategy
+            .next_guess(self.low, self.high)
+            .clamp(self.low, self.high);
+        self.current_guess = Some(guess);
+        self.attempts += 1;
+        guess
+    }
+
+    // Returns the number once it has been found, or the contradiction if the answers don't
+    // leave any candidates.
+    pub fn answer(&mut self, answer: Answer) -> Result<Option<u32>, Contradiction> {
+        let guess = self.next_guess();
+        self.current_guess = None;
+
+        match answer {
+            Answer::Correct => return Ok(Some(guess)),
+            Answer::Higher => {
+                self.above = Some(guess);
+                if guess == u32::MAX || guess + 1 > self.high {
+                    return Err(self.contradiction());
+                }
+                self.low = guess + 1;
+            }
+            Answer::Lower => {
+                self.below = Some(guess);
+                if guess == 0 || guess - 1 < self.low {
+                    return Err(self.contradiction());
+                }
+                self.high = guess - 1;
+            }
+        }
+        Ok(None)
+    }
+
+    fn contradiction(&self) -> Contradiction {
+        Contradiction {
+            above: self.above,
+            below: self.below,
+            min: self.config.min,
+            max: self.config.max,
+        }
+    }
+}
+
+pub fn play_reverse<R: BufRead, W: Write>(
+    game: &mut ReverseGame,
+    mut input: R,
+    mut output: W,
+) -> io::Result<ReverseOutcome> {
+    writeln!(
+        output,
+        "Think of a number between {} and {}, I'll try to guess it.",
+        game.config.min, game.config.max
+    )?;
+    writeln!(output, "Answer with higher, lower or correct (h/l/c).")?;
+
+    loop {
+        let guess = game.next_guess();
+        writeln!(output, "Is it {guess}?")?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim() == "quit" {
+            return Ok(ReverseOutcome::Quit);
+        }
+        let answer = match Answer::parse(&line) {
+            Some(answer) => answer,
+            None => {
+                writeln!(output, "Please answer higher, lower or correct.")?;
+                continue;
+            }
+        };
+
+        match game.answer(answer) {
+            Ok(Some(number)) => {
+                let attempts = game.attempts();
+                writeln!(
+                    output,
+                    "Got it! Your number is {number}, found in {attempts} guesses."
+                )?;
+                return Ok(ReverseOutcome::Found { number, attempts });
+            }
+            Ok(None) => {}
+            Err(contradiction) => {
+                writeln!(output, "That can't be right: {contradiction}.")?;
+                return Ok(ReverseOutcome::Contradiction(contradiction));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::BinarySearch;
+
+    fn run(input: &str) -> (ReverseOutcome, String) {
+        let mut game = ReverseGame::new(Config::default(), Box::new(BinarySearch));
+        let mut output = Vec::new();
+        let outcome = play_reverse(&mut game, input.as_bytes(), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn finds_the_number_with_binary_search() {
+        // 50 -> higher, 75 -> lower, 62 -> correct
+        let (outcome, output) = run("h\nwhat\nlower\nc\n");
+        assert_eq!(
+            outcome,
+            ReverseOutcome::Found {
+                number: 62,
+                attempts: 3
+            }
+        );
+        assert!(output.contains("Please answer higher, lower or correct."));
+    }
+
+    #[test]
+    fn inconsistent_answers_are_reported() {
+        // 50 -> higher, 75 -> lower, 62 -> lower, 56 -> lower, 53 -> lower, 51 -> lower
+        let (outcome, output) = run("h\nl\nl\nl\nl\nl\n");
+        let expected = Contradiction {
+            above: Some(50),
+            below: Some(51),
+            min: 1,
+            max: 100,
+        };
+        assert_eq!(outcome, ReverseOutcome::Contradiction(expected));
+        assert!(output.contains("higher than 50 and lower than 51"));
+    }
+
+    #[test]
+    fn answering_past_the_edge_of_the_range_is_a_contradiction() {
+        let mut game = ReverseGame::new(
+            Config {
+                min: 1,
+                max: 1,
+                ..Config::default()
+            },
+            Box::new(BinarySearch),
+        );
+        assert_eq!(game.next_guess(), 1);
+        let contradiction = game.answer(Answer::Lower).unwrap_err();
+        assert_eq!(
+            contradiction.to_string(),
+            "you said your number is lower than 1, but it can't be smaller than 1"
+        );
+    }
+}
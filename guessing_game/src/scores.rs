@@ -0,0 +1,318 @@
+use crate::config::{Config, Difficulty};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const TOP_SCORES: usize = 10;
+
+// One finished game. Scores are kept as tab separated lines so the file stays easy to read
+// (and fix) by hand:
+// player  won  attempts  min  max  difficulty  duration_ms  timestamp
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub player: String,
+    pub won: bool,
+    pub attempts: u32,
+    pub min: u32,
+    pub max: u32,
+    pub difficulty: Option<Difficulty>,
+    pub duration: Duration,
+    // seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+impl Score {
+    pub fn new(
+        player: &str,
+        config: &Config,
+        won: bool,
+        attempts: u32,
+        duration: Duration,
+    ) -> Score {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        Score {
+            // tabs and newlines would break the file format
+            player: player.replace(['\t', '\n', '\r'], " "),
+            won,
+            attempts,
+            min: config.min,
+            max: config.max,
+            difficulty: config.difficulty,
+            duration,
+            timestamp,
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.player,
+            if self.won { "won" } else { "lost" },
+            self.attempts,
+            self.min,
+            self.max,
+            difficulty_name(self.difficulty),
+            self.duration.as_millis(),
+            self.timestamp
+        )
+    }
+
+    pub fn from_line(line: &str) -> Result<Score, String> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 8 {
+            return Err(format!("expected 8 fields, found {}", fields.len()));
+        }
+        let won = match fields[1] {
+            "won" => true,
+            "lost" => false,
+            other => return Err(format!("unknown result `{other}`")),
+        };
+        let difficulty = match fields[5] {
+            "custom" => None,
+            name => Some(Difficulty::parse(name)?),
+        };
+        Ok(Score {
+            player: fields[0].to_string(),
+            won,
+            attempts: parse_field(fields[2], "attempts")?,
+            min: parse_field(fields[3], "min")?,
+            max: parse_field(fields[4], "max")?,
+            difficulty,
+            duration: Duration::from_millis(parse_field(fields[6], "duration")?),
+            timestamp: parse_field(fields[7], "timestamp")?,
+        })
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(raw: &str, name: &str) -> Result<T, String> {
+    raw.parse().map_err(|_| format!("invalid {name} `{raw}`"))
+}
+
+fn difficulty_name(difficulty: Option<Difficulty>) -> String {
+    match difficulty {
+        Some(difficulty) => difficulty.to_string(),
+        None => String::from("custom"),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScoreBoard {
+    pub scores: Vec<Score>,
+    // lines that couldn't be parsed, as (line number, reason)
+    pub skipped: Vec<(usize, String)>,
+}
+
+impl ScoreBoard {
+    // A missing file is just an empty board, and broken lines are skipped instead of failing
+    // the whole load so one bad edit doesn't wipe out everybody's scores.
+    pub fn load(path: &Path) -> io::Result<ScoreBoard> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(ScoreBoard::default()),
+            Err(error) => return Err(error),
+        };
+
+        let mut board = ScoreBoard::default();
+        for (index, line) in String::from_utf8_lossy(&bytes).lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match Score::from_line(line) {
+                Ok(score) => board.scores.push(score),
+                Err(reason) => board.skipped.push((index + 1, reason)),
+            }
+        }
+        Ok(board)
+    }
+
+    pub fn append(path: &Path, score: &Score) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", score.to_line())
+    }
+
+    // Winning games only, fewest attempts first and the faster game breaking ties. Each preset
+    // is one table, but custom games only share a table with games over the same range since
+    // attempts on 1..=10 and 1..=10000 can't be compared.
+    pub fn leaderboard(&self, difficulty: Option<Difficulty>, min: u32, max: u32) -> Vec<&Score> {
+        let mut scores: Vec<&Score> = self
+            .scores
+            .iter()
+            .filter(|score| score.won && score.difficulty == difficulty)
+            .filter(|score| difficulty.is_some() || (score.min, score.max) == (min, max))
+            .collect();
+        scores.sort_by_key(|score| (score.attempts, score.duration));
+        scores
+    }
+
+    pub fn print<W: Write>(&self, mut output: W) -> io::Result<()> {
+        let mut groups: Vec<(Option<Difficulty>, u32, u32)> =
+            [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+                .into_iter()
+                .map(|difficulty| {
+                    let (min, max, _) = difficulty.preset();
+                    (Some(difficulty), min, max)
+                })
+                .collect();
+        let mut custom_ranges: Vec<(u32, u32)> = self
+            .scores
+            .iter()
+            .filter(|score| score.difficulty.is_none())
+            .map(|score| (score.min, score.max))
+            .collect();
+        custom_ranges.sort_unstable();
+        custom_ranges.dedup();
+        groups.extend(custom_ranges.into_iter().map(|(min, max)| (None, min, max)));
+        let mut printed_any = false;
+
+        for (difficulty, min, max) in groups {
+            let scores = self.leaderboard(difficulty, min, max);
+            if scores.is_empty() {
+                continue;
+            }
+            printed_any = true;
+            match difficulty {
+                Some(difficulty) => writeln!(output, "== {difficulty} ==")?,
+                None => writeln!(output, "== custom {min}..={max} ==")?,
+            }
+            for (rank, score) in scores.iter().take(TOP_SCORES).enumerate() {
+                writeln!(
+                    output,
+                    "{:>2}. {:<16} {:>3} attempts  {:>6.1}s  {}..={}  {}",
+                    rank + 1,
+                    score.player,
+                    score.attempts,
+                    score.duration.as_secs_f64(),
+                    score.min,
+                    score.max,
+                    format_timestamp(score.timestamp)
+                )?;
+            }
+        }
+
+        if !printed_any {
+            writeln!(output, "No high scores yet.")?;
+        }
+        for (line, reason) in &self.skipped {
+            writeln!(
+                output,
+                "warning: skipped line {line} of the score file: {reason}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// "YYYY-MM-DD HH:MM" in UTC, using the days-to-civil-date algorithm so we don't need a date crate
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3_600,
+        seconds % 3_600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(player: &str, attempts: u32, difficulty: Option<Difficulty>) -> Score {
+        Score {
+            player: player.to_string(),
+            won: true,
+            attempts,
+            min: 1,
+            max: 100,
+            difficulty,
+            duration: Duration::from_millis(1_500),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn score_lines_round_trip() {
+        let original = score("ferris", 5, Some(Difficulty::Hard));
+        assert_eq!(Score::from_line(&original.to_line()), Ok(original));
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_board() {
+        let board = ScoreBoard::load(Path::new("this/file/does/not/exist.tsv")).unwrap();
+        assert!(board.scores.is_empty());
+        assert!(board.skipped.is_empty());
+    }
+
+    #[test]
+    fn corrupt_lines_are_skipped_and_the_rest_is_sorted() {
+        let path =
+            std::env::temp_dir().join(format!("guessing_game_scores_{}.tsv", std::process::id()));
+        let lines = [
+            score("slow", 9, None).to_line(),
+            String::from("garbage"),
+            score("fast", 3, None).to_line(),
+            score("easy", 1, Some(Difficulty::Easy)).to_line(),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+
+        let board = ScoreBoard::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(board.skipped.len(), 1);
+        assert_eq!(board.skipped[0].0, 2);
+        let custom: Vec<&str> = board
+            .leaderboard(None, 1, 100)
+            .iter()
+            .map(|score| score.player.as_str())
+            .collect();
+        assert_eq!(custom, ["fast", "slow"]);
+    }
+
+    #[test]
+    fn custom_games_only_share_a_table_with_the_same_range() {
+        let mut wide = score("wide", 2, None);
+        wide.max = 10_000;
+        let board = ScoreBoard {
+            scores: vec![score("narrow", 4, None), wide],
+            skipped: Vec::new(),
+        };
+
+        assert_eq!(board.leaderboard(None, 1, 100)[0].player, "narrow");
+        assert_eq!(board.leaderboard(None, 1, 10_000)[0].player, "wide");
+        assert_eq!(board.leaderboard(None, 1, 100).len(), 1);
+
+        let mut printed = Vec::new();
+        board.print(&mut printed).unwrap();
+        let printed = String::from_utf8(printed).unwrap();
+        assert!(printed.contains("== custom 1..=100 ==\n 1. narrow"));
+        assert!(printed.contains("== custom 1..=10000 ==\n 1. wide"));
+    }
+
+    #[test]
+    fn timestamps_are_formatted_as_utc_dates() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13");
+    }
+}
@@ -0,0 +1,385 @@
+use crate::{
+    config::Config,
+    game::{GuessError, Proximity},
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{cmp::Ordering, fmt, num::IntErrorKind};
+
+// Anything that can be hidden and guessed at with Too small / Too big feedback. `Game` only
+// talks to the secret through this trait, so the same loop plays numbers, letters and words.
+pub trait Secret {
+    type Guess: PartialEq + fmt::Display;
+
+    // what's being guessed, as in "the number was 42"
+    fn noun(&self) -> &'static str;
+
+    // The intro line shown before the first guess.
+    fn describe(&self, config: &Config) -> String;
+
+    fn reveal(&self) -> String;
+
+    fn parse_guess(&self, input: &str, config: &Config) -> Result<Self::Guess, GuessError>;
+
+    // How the guess compares to the secret, so `Ordering::Less` means "too small".
+    fn compare(&self, guess: &Self::Guess) -> Ordering;
+
+    fn proximity(&self, _guess: &Self::Guess, _config: &Config) -> Option<Proximity> {
+        None
+    }
+}
+
+// Turns the distance between a guess and the secret into a hint, relative to the range size.
+fn proximity_for(distance: f64, range: f64) -> Proximity {
+    if distance * 50.0 <= range {
+        Proximity::Burning
+    } else if distance * 10.0 <= range {
+        Proximity::Hot
+    } else if distance * 4.0 <= range {
+        Proximity::Warm
+    } else {
+        Proximity::Cold
+    }
+}
+
+// The classic game: a whole number between `config.min` and `config.max`.
+impl Secret for u32 {
+    type Guess = u32;
+
+    fn noun(&self) -> &'static str {
+        "number"
+    }
+
+    fn describe(&self, config: &Config) -> String {
+        format!("The number is between {} and {}.", config.min, config.max)
+    }
+
+    fn reveal(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse_guess(&self, input: &str, config: &Config) -> Result<u32, GuessError> {
+        let guess: u32 = match input.parse() {
+            Ok(guess) => guess,
+            Err(error) => {
+                let is_negative = input.strip_prefix('-').is_some_and(|digits| {
+                    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+                });
+                return Err(match error.kind() {
+                    IntErrorKind::PosOverflow => GuessError::TooLarge,
+                    _ if is_negative => GuessError::Negative,
+                    _ => GuessError::NotANumber(input.to_string()),
+                });
+            }
+        };
+
+        if guess < config.min || guess > config.max {
+            return Err(GuessError::OutOfRange {
+                guess,
+                min: config.min,
+                max: config.max,
+            });
+        }
+        Ok(guess)
+    }
+
+    fn compare(&self, guess: &u32) -> Ordering {
+        guess.cmp(self)
+    }
+
+    fn proximity(&self, guess: &u32, config: &Config) -> Option<Proximity> {
+        let distance = guess.abs_diff(*self);
+        let range = u64::from(config.max - config.min) + 1;
+        Some(proximity_for(f64::from(distance), range as f64))
+    }
+}
+
+// A letter from a to z, where `a` is the smallest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Letter(char);
+
+impl Letter {
+    pub fn new(letter: char) -> Option<Letter> {
+        let letter = letter.to_ascii_lowercase();
+        letter.is_ascii_lowercase().then_some(Letter(letter))
+    }
+
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Letter {
+        Letter(rng.gen_range('a'..='z'))
+    }
+}
+
+impl Secret for Letter {
+    type Guess = char;
+
+    fn noun(&self) -> &'static str {
+        "letter"
+    }
+
+    fn describe(&self, _config: &Config) -> String {
+        String::from("The secret is a letter from a to z.")
+    }
+
+    fn reveal(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn parse_guess(&self, input: &str, _config: &Config) -> Result<char, GuessError> {
+        let mut chars = input.chars();
+        match (chars.next().and_then(Letter::new), chars.next()) {
+            (Some(Letter(letter)), None) => Ok(letter),
+            _ => Err(GuessError::Invalid(format!(
+                "`{input}` is not a single letter from a to z."
+            ))),
+        }
+    }
+
+    fn compare(&self, guess: &char) -> Ordering {
+        guess.cmp(&self.0)
+    }
+
+    fn proximity(&self, guess: &char, _config: &Config) -> Option<Proximity> {
+        let distance = (*guess as u32).abs_diff(self.0 as u32);
+        Some(proximity_for(f64::from(distance), 26.0))
+    }
+}
+
+// A real number between `config.min` and `config.max`. Guesses within `tolerance` of it win,
+// since nobody is going to type every decimal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Float {
+    value: f64,
+    tolerance: f64,
+}
+
+impl Float {
+    pub fn new(value: f64, tolerance: f64) -> Float {
+        Float {
+            value,
+            tolerance: tolerance.abs(),
+        }
+    }
+
+    pub fn random<R: Rng + ?Sized>(config: &Config, tolerance: f64, rng: &mut R) -> Float {
+        let value = rng.gen_range(f64::from(config.min)..=f64::from(config.max));
+        Float::new(value, tolerance)
+    }
+}
+
+impl Secret for Float {
+    type Guess = f64;
+
+    fn noun(&self) -> &'static str {
+        "number"
+    }
+
+    fn describe(&self, config: &Config) -> String {
+        format!(
+            "The number is a decimal between {} and {}, anything within {} of it wins.",
+            config.min, config.max, self.tolerance
+        )
+    }
+
+    fn reveal(&self) -> String {
+        format!("{:.3}", self.value)
+    }
+
+    fn parse_guess(&self, input: &str, config: &Config) -> Result<f64, GuessError> {
+        let guess: f64 = input
+            .parse()
+            .map_err(|_| GuessError::NotANumber(input.to_string()))?;
+        // "NaN" and "inf" parse fine but can't be compared sensibly
+        if !guess.is_finite() {
+            return Err(GuessError::NotANumber(input.to_string()));
+        }
+        if guess < f64::from(config.min) || guess > f64::from(config.max) {
+            return Err(GuessError::Invalid(format!(
+                "{guess} is out of range, the number is between {} and {}.",
+                config.min, config.max
+            )));
+        }
+        Ok(guess)
+    }
+
+    fn compare(&self, guess: &f64) -> Ordering {
+        if (guess - self.value).abs() <= self.tolerance {
+            Ordering::Equal
+        } else {
+            guess.total_cmp(&self.value)
+        }
+    }
+
+    fn proximity(&self, guess: &f64, config: &Config) -> Option<Proximity> {
+        let range = f64::from(config.max - config.min).max(1.0);
+        Some(proximity_for((guess - self.value).abs(), range))
+    }
+}
+
+pub const DEFAULT_WORDS: [&str; 30] = [
+    "apple",
+    "banana",
+    "borrow",
+    "cargo",
+    "castle",
+    "closure",
+    "compiler",
+    "crab",
+    "enum",
+    "ferris",
+    "garden",
+    "guess",
+    "harbor",
+    "iterator",
+    "lifetime",
+    "macro",
+    "match",
+    "module",
+    "ownership",
+    "pattern",
+    "river",
+    "rust",
+    "slice",
+    "string",
+    "struct",
+    "trait",
+    "vector",
+    "waffle",
+    "yellow",
+    "zebra",
+];
+
+// A word picked from a dictionary, compared alphabetically: "apple" is smaller than "banana".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word(String);
+
+impl Word {
+    pub fn new(word: &str) -> Word {
+        Word(word.trim().to_lowercase())
+    }
+
+    // Only words made of letters can be guessed, "ice-cream" could never be typed in.
+    pub fn is_guessable(word: &str) -> bool {
+        let word = word.trim();
+        !word.is_empty() && word.chars().all(char::is_alphabetic)
+    }
+
+    // Returns `None` if the dictionary has no usable words.
+    pub fn random<R: Rng + ?Sized>(dictionary: &[String], rng: &mut R) -> Option<Word> {
+        dictionary
+            .iter()
+            .filter(|word| Word::is_guessable(word))
+            .collect::<Vec<_>>()
+            .choose(rng)
+            .map(|word| Word::new(word))
+    }
+}
+
+// The words in a dictionary file, one per line, leaving out the ones that can't be guessed.
+pub fn load_dictionary(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|word| Word::is_guessable(word))
+        .map(|word| word.trim().to_string())
+        .collect()
+}
+
+impl Secret for Word {
+    type Guess = String;
+
+    fn noun(&self) -> &'static str {
+        "word"
+    }
+
+    fn describe(&self, _config: &Config) -> String {
+        format!(
+            "The secret is a {} letter word, words are compared alphabetically.",
+            self.0.chars().count()
+        )
+    }
+
+    fn reveal(&self) -> String {
+        self.0.clone()
+    }
+
+    fn parse_guess(&self, input: &str, _config: &Config) -> Result<String, GuessError> {
+        if !input.chars().all(char::is_alphabetic) {
+            return Err(GuessError::Invalid(format!(
+                "`{input}` is not a word, use letters only."
+            )));
+        }
+        Ok(input.to_lowercase())
+    }
+
+    fn compare(&self, guess: &String) -> Ordering {
+        guess.cmp(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{play, Game, Outcome};
+
+    fn run<S: Secret>(mut game: Game<S>, input: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome = play(&mut game, input.as_bytes(), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    fn responses(output: &str) -> Vec<&str> {
+        output
+            .lines()
+            .filter(|line| line.starts_with("Too") || line.starts_with("You win"))
+            .collect()
+    }
+
+    #[test]
+    fn letters_are_ordered_alphabetically() {
+        let game = Game::from_secret(Config::default(), Letter::new('m').unwrap());
+        let (outcome, output) = run(game, "c\nZ\nhello\nM\n");
+
+        assert_eq!(outcome, Outcome::Won { attempts: 3 });
+        assert_eq!(responses(&output), ["Too small!", "Too big!", "You win!"]);
+        assert!(output.contains("`hello` is not a single letter"));
+    }
+
+    #[test]
+    fn floats_win_within_the_tolerance() {
+        let game = Game::from_secret(Config::default(), Float::new(42.5, 0.25));
+        let (outcome, output) = run(game, "40\n43\nNaN\n42.7\n");
+
+        assert_eq!(outcome, Outcome::Won { attempts: 3 });
+        assert_eq!(responses(&output), ["Too small!", "Too big!", "You win!"]);
+        assert!(output.contains("`NaN` is not a number"));
+    }
+
+    #[test]
+    fn words_are_compared_lexicographically() {
+        let config = Config {
+            max_attempts: Some(2),
+            ..Config::default()
+        };
+        let game = Game::from_secret(config, Word::new("match"));
+        let (outcome, output) = run(game, "apple\nr2d2\nzebra\n");
+
+        assert_eq!(outcome, Outcome::OutOfAttempts);
+        assert_eq!(responses(&output), ["Too small!", "Too big!"]);
+        assert!(output.ends_with("Out of attempts, the word was match.\n"));
+    }
+
+    #[test]
+    fn random_words_come_from_the_dictionary() {
+        let dictionary: Vec<String> = DEFAULT_WORDS.iter().map(|word| word.to_string()).collect();
+        let word = Word::random(&dictionary, &mut rand::thread_rng()).unwrap();
+        assert!(DEFAULT_WORDS.contains(&word.reveal().as_str()));
+        assert_eq!(Word::random(&[], &mut rand::thread_rng()), None);
+    }
+
+    #[test]
+    fn words_that_cant_be_guessed_are_left_out() {
+        assert_eq!(
+            load_dictionary("apple\nice-cream\n\n  zebra \nr2d2\n"),
+            ["apple", "zebra"]
+        );
+        let unusable = vec![String::from("ice-cream"), String::from("don't")];
+        assert_eq!(Word::random(&unusable, &mut rand::thread_rng()), None);
+    }
+}
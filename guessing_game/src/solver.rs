@@ -0,0 +1,271 @@
+use crate::{
+    config::Config,
+    game::{Game, Outcome},
+};
+use rand::Rng;
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{self, Write},
+};
+
+// A way of picking the next guess. The solver keeps `low..=high` narrowed down with the
+// `Ordering` each guess produces, so the secret number is always somewhere inside it.
+pub trait Strategy {
+    fn next_guess(&mut self, low: u32, high: u32) -> u32;
+}
+
+// Halves the remaining range every time: O(log n) guesses.
+pub struct BinarySearch;
+
+impl Strategy for BinarySearch {
+    fn next_guess(&mut self, low: u32, high: u32) -> u32 {
+        low + (high - low) / 2
+    }
+}
+
+// Counts up from the bottom of the range: O(n) guesses.
+pub struct Linear;
+
+impl Strategy for Linear {
+    fn next_guess(&mut self, low: u32, _high: u32) -> u32 {
+        low
+    }
+}
+
+// Picks anywhere in the remaining range, which still narrows it down but less evenly.
+pub struct RandomGuess<R: Rng> {
+    rng: R,
+}
+
+impl<R: Rng> RandomGuess<R> {
+    pub fn new(rng: R) -> RandomGuess<R> {
+        RandomGuess { rng }
+    }
+}
+
+impl<R: Rng> Strategy for RandomGuess<R> {
+    fn next_guess(&mut self, low: u32, high: u32) -> u32 {
+        self.rng.gen_range(low..=high)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyKind {
+    BinarySearch,
+    Random,
+    Linear,
+}
+
+impl StrategyKind {
+    pub const ALL: [StrategyKind; 3] = [
+        StrategyKind::BinarySearch,
+        StrategyKind::Random,
+        StrategyKind::Linear,
+    ];
+
+    pub fn parse(name: &str) -> Result<StrategyKind, String> {
+        match name.to_lowercase().as_str() {
+            "binary" | "binary-search" => Ok(StrategyKind::BinarySearch),
+            "random" => Ok(StrategyKind::Random),
+            "linear" => Ok(StrategyKind::Linear),
+            other => Err(format!(
+                "unknown strategy `{other}`, expected binary, random, linear or all"
+            )),
+        }
+    }
+
+    pub fn build(self) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::BinarySearch => Box::new(BinarySearch),
+            StrategyKind::Random => Box::new(RandomGuess::new(rand::thread_rng())),
+            StrategyKind::Linear => Box::new(Linear),
+        }
+    }
+}
+
+impl fmt::Display for StrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            StrategyKind::BinarySearch => "binary search",
+            StrategyKind::Random => "random",
+            StrategyKind::Linear => "linear",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Plays `game` to the end with `strategy`, returning every guess made along the way.
+pub fn solve(game: &mut Game, strategy: &mut dyn Strategy) -> Vec<(u32, Ordering)> {
+    let mut low = game.config().min;
+    let mut high = game.config().max;
+    let mut guesses = Vec::new();
+
+    while !game.is_over() {
+        let guess = strategy.next_guess(low, high).clamp(low, high);
+        let ordering = match game.guess(guess) {
+            Some(ordering) => ordering,
+            None => break,
+        };
+        guesses.push((guess, ordering));
+
+        match ordering {
+            Ordering::Less => low = guess + 1,
+            Ordering::Greater => high = guess - 1,
+            Ordering::Equal => break,
+        }
+    }
+
+    guesses
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Benchmark {
+    pub games: u32,
+    pub average: f64,
+    pub best: u32,
+    pub worst: u32,
+    // games that needed more guesses than the config's attempt budget allows
+    pub over_budget: u32,
+}
+
+// Plays `games` rounds with fresh secret numbers from `rng`. The attempt budget is lifted so
+// every game runs to a win, and games that went past it are counted instead.
+pub fn benchmark<R: Rng + ?Sized>(
+    config: &Config,
+    strategy: &mut dyn Strategy,
+    games: u32,
+    rng: &mut R,
+) -> Benchmark {
+    let unlimited = Config {
+        max_attempts: None,
+        ..config.clone()
+    };
+    let mut total = 0u64;
+    let mut best = u32::MAX;
+    let mut worst = 0;
+    let mut over_budget = 0;
+
+    for _ in 0..games {
+        let mut game = Game::new(unlimited.clone(), rng);
+        solve(&mut game, strategy);
+        let attempts = match game.outcome() {
+            Outcome::Won { attempts } => attempts,
+            _ => game.attempts(),
+        };
+
+        total += u64::from(attempts);
+        best = best.min(attempts);
+        worst = worst.max(attempts);
+        if config.max_attempts.is_some_and(|max| attempts > max) {
+            over_budget += 1;
+        }
+    }
+
+    Benchmark {
+        games,
+        average: if games == 0 {
+            0.0
+        } else {
+            total as f64 / f64::from(games)
+        },
+        best: if games == 0 { 0 } else { best },
+        worst,
+        over_budget,
+    }
+}
+
+// The most guesses binary search can ever need for this range: ceil(log2(n + 1)).
+pub fn binary_search_bound(config: &Config) -> u32 {
+    let size = u64::from(config.max - config.min) + 1;
+    u64::BITS - size.leading_zeros()
+}
+
+pub fn print_report<W: Write>(
+    mut output: W,
+    config: &Config,
+    results: &[(StrategyKind, Benchmark)],
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "Range {}..={} ({} numbers), binary search never needs more than {} guesses.",
+        config.min,
+        config.max,
+        u64::from(config.max - config.min) + 1,
+        binary_search_bound(config)
+    )?;
+    for (kind, result) in results {
+        write!(
+            output,
+            "{:<14} {} games: average {:.2}, best {}, worst {}",
+            kind.to_string(),
+            result.games,
+            result.average,
+            result.best,
+            result.worst
+        )?;
+        match config.max_attempts {
+            Some(max_attempts) => writeln!(
+                output,
+                ", {} over the {max_attempts} attempt budget",
+                result.over_budget
+            )?,
+            None => writeln!(output)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn every_strategy_finds_every_number() {
+        let config = Config {
+            min: 1,
+            max: 30,
+            ..Config::default()
+        };
+        for kind in StrategyKind::ALL {
+            for secret in 1..=30 {
+                let mut game = Game::with_secret(config.clone(), secret);
+                let guesses = solve(&mut game, kind.build().as_mut());
+                assert_eq!(guesses.last(), Some(&(secret, Ordering::Equal)));
+            }
+        }
+    }
+
+    #[test]
+    fn binary_search_stays_within_log2_guesses() {
+        let config = Config::default();
+        let mut rng = StdRng::seed_from_u64(1);
+        let result = benchmark(&config, &mut BinarySearch, 500, &mut rng);
+
+        assert_eq!(binary_search_bound(&config), 7);
+        assert!(result.worst <= 7);
+        assert!(result.average < f64::from(result.worst));
+    }
+
+    #[test]
+    fn linear_worst_case_is_the_whole_range() {
+        let config = Config {
+            min: 1,
+            max: 10,
+            max_attempts: Some(5),
+            difficulty: None,
+        };
+        let mut game = Game::with_secret(
+            Config {
+                max_attempts: None,
+                ..config.clone()
+            },
+            10,
+        );
+        assert_eq!(solve(&mut game, &mut Linear).len(), 10);
+
+        let result = benchmark(&config, &mut Linear, 200, &mut StdRng::seed_from_u64(3));
+        assert!(result.over_budget > 0);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    config::{Config, Difficulty},
+    game::{respond, Game},
+};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+// A recorded game: the seed and config needed to get the same secret number back, and every
+// line the player typed with what the game answered. The file looks like this:
+//
+//   seed 1234
+//   min 1
+//   max 100
+//   max-attempts none
+//   difficulty custom
+//   > 50
+//   < Too big!
+//   < You're warm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub seed: u64,
+    pub config: Config,
+    pub turns: Vec<Turn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turn {
+    pub input: String,
+    pub responses: Vec<String>,
+}
+
+// A turn where replaying the input didn't produce what was recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub turn: usize,
+    pub input: String,
+    pub expected: Vec<String>,
+    pub actual: Vec<String>,
+}
+
+impl Transcript {
+    pub fn new(seed: u64, config: Config) -> Transcript {
+        Transcript {
+            seed,
+            config,
+            turns: Vec::new(),
+        }
+    }
+
+    pub fn game(&self) -> Game {
+        Game::from_seed(self.config.clone(), self.seed)
+    }
+
+    pub fn push(&mut self, input: &str, responses: &[String]) {
+        self.turns.push(Turn {
+            input: input.to_string(),
+            responses: responses.to_vec(),
+        });
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "seed {}\nmin {}\nmax {}\nmax-attempts {}\ndifficulty {}\n",
+            self.seed,
+            self.config.min,
+            self.config.max,
+            match self.config.max_attempts {
+                Some(max_attempts) => max_attempts.to_string(),
+                None => String::from("none"),
+            },
+            match self.config.difficulty {
+                Some(difficulty) => difficulty.to_string(),
+                None => String::from("custom"),
+            }
+        );
+        for turn in &self.turns {
+            text.push_str(&format!("> {}\n", turn.input));
+            for response in &turn.responses {
+                text.push_str(&format!("< {response}\n"));
+            }
+        }
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Transcript, String> {
+        let mut transcript = Transcript::new(0, Config::default());
+        let mut seen_seed = false;
+
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| format!("line {}: {message}", index + 1);
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(input) = line.strip_prefix("> ").or(line.strip_prefix('>')) {
+                transcript.turns.push(Turn {
+                    input: input.to_string(),
+                    responses: Vec::new(),
+                });
+                continue;
+            }
+            if let Some(response) = line.strip_prefix("< ").or(line.strip_prefix('<')) {
+                match transcript.turns.last_mut() {
+                    Some(turn) => turn.responses.push(response.to_string()),
+                    None => return Err(error(String::from("response before any input"))),
+                }
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(' ')
+                .ok_or_else(|| error(format!("don't know what to do with `{line}`")))?;
+            let number = |value: &str| {
+                value
+                    .parse::<u32>()
+                    .map_err(|_| error(format!("`{value}` is not a valid {key}")))
+            };
+            match key {
+                "seed" => {
+                    transcript.seed = value
+                        .parse()
+                        .map_err(|_| error(format!("`{value}` is not a valid seed")))?;
+                    seen_seed = true;
+                }
+                "min" => transcript.config.min = number(value)?,
+                "max" => transcript.config.max = number(value)?,
+                "max-attempts" => {
+                    transcript.config.max_attempts = match value {
+                        "none" => None,
+                        value => Some(number(value)?),
+                    }
+                }
+                "difficulty" => {
+                    transcript.config.difficulty = match value {
+                        "custom" => None,
+                        value => Some(Difficulty::parse(value).map_err(error)?),
+                    }
+                }
+                other => return Err(error(format!("unknown setting `{other}`"))),
+            }
+        }
+
+        if !seen_seed {
+            return Err(String::from("the transcript has no seed"));
+        }
+        transcript.config.validate()?;
+        Ok(transcript)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    pub fn load(path: &Path) -> Result<Transcript, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("couldn't read {}: {error}", path.display()))?;
+        Transcript::parse(&text)
+    }
+
+    // Plays the recorded inputs against a fresh game built from the same seed, printing each
+    // turn and returning every turn whose responses came out different.
+    pub fn replay<W: Write>(&self, mut output: W) -> io::Result<Vec<Mismatch>> {
+        let mut game = self.game();
+        let mut mismatches = Vec::new();
+        writeln!(
+            output,
+            "Replaying seed {}, the secret number is {}.",
+            self.seed,
+            game.secret_number()
+        )?;
+
+        for (index, turn) in self.turns.iter().enumerate() {
+            writeln!(output, "> {}", turn.input)?;
+            let actual = respond(&mut game, &turn.input);
+            for response in &actual {
+                writeln!(output, "< {response}")?;
+            }
+
+            if actual != turn.responses {
+                writeln!(output, "!! turn {} differs from the recording:", index + 1)?;
+                for response in &turn.responses {
+                    writeln!(output, "!! expected < {response}")?;
+                }
+                mismatches.push(Mismatch {
+                    turn: index + 1,
+                    input: turn.input.clone(),
+                    expected: turn.responses.clone(),
+                    actual,
+                });
+            }
+        }
+
+        writeln!(output, "Final outcome: {}", game.outcome())?;
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::play_turns;
+
+    fn record(seed: u64, input: &str) -> Transcript {
+        let mut transcript = Transcript::new(seed, Config::from_difficulty(Difficulty::Easy));
+        let mut game = transcript.game();
+        play_turns(
+            &mut game,
+            input.as_bytes(),
+            io::sink(),
+            |line, responses| transcript.push(line, responses),
+        )
+        .unwrap();
+        transcript
+    }
+
+    #[test]
+    fn transcripts_round_trip_through_text() {
+        let transcript = record(99, "25\nnope\n25\n12\nquit\n");
+        assert_eq!(transcript.turns.len(), 5);
+        assert_eq!(Transcript::parse(&transcript.to_text()), Ok(transcript));
+    }
+
+    #[test]
+    fn replaying_a_recording_reproduces_it_exactly() {
+        let transcript = record(5, "25\n37\n31\n");
+        assert!(transcript.replay(io::sink()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn replay_reports_turns_that_differ() {
+        let mut transcript = record(5, "0\n");
+        transcript.turns[0].responses = vec![String::from("You win!")];
+
+        let mismatches = transcript.replay(io::sink()).unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].turn, 1);
+        assert_eq!(mismatches[0].expected, ["You win!"]);
+    }
+
+    #[test]
+    fn transcripts_without_a_seed_are_rejected() {
+        assert!(Transcript::parse("min 1\nmax 10\n").is_err());
+        assert!(Transcript::parse("seed 1\n< Too big!\n").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+// Compares the linear fibonacci functions with fast doubling, and the original i32 loop with
+// `checked_fibonacci`. Run with `cargo bench`.
+use practices::fibonacci;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn time<T>(runs: u32, mut f: impl FnMut() -> T) -> Duration {
+    let started = Instant::now();
+    for _ in 0..runs {
+        black_box(f());
+    }
+    started.elapsed() / runs
+}
+
+fn main() {
+    println!("{:>8}  {:>14}  {:>14}", "n", "iterative", "fast doubling");
+    for n in [100, 1_000, 10_000, 50_000] {
+        let runs = if n > 10_000 { 3 } else { 20 };
+        let iterative = time(runs, || fibonacci::fibonacci_big(black_box(n)));
+        let fast = time(runs, || fibonacci::fibonacci_fast(black_box(n)));
+        println!("{n:>8}  {iterative:>14.2?}  {fast:>14.2?}");
+    }
+
+    // the original loop counts from 1 and overflows an i32 past the 47th, which is F(46)
+    println!();
+    println!("{:>8}  {:>14}  {:>14}", "nth", "original", "checked");
+    for nth in [10, 30, 47] {
+        let original = time(100_000, || {
+            fibonacci::generate_nth_fibonacci_number(black_box(nth))
+        });
+        let checked = time(100_000, || fibonacci::checked_fibonacci(black_box(nth - 1)));
+        println!("{nth:>8}  {original:>14.2?}  {checked:>14.2?}");
+    }
+
+    println!();
+    let n = 186;
+    let iterative = time(10_000, || fibonacci::fibonacci_u128(black_box(n)));
+    let modular = time(10_000, || fibonacci::fibonacci_mod(black_box(n), u64::MAX));
+    println!("F({n}) as u128: {iterative:.2?}, F({n}) mod 2^64-1: {modular:.2?}");
+
+    let n = 1_000_000_000_000_000_000;
+    let modular = time(10_000, || fibonacci::fibonacci_mod(black_box(n), 1_000_000_007));
+    println!("F(10^18) mod 1e9+7: {modular:.2?}");
+}
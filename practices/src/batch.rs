@@ -0,0 +1,608 @@
+use crate::temperature_converter::{display_value, Scale, Temperature};
+use std::{
+  fmt,
+  io::{self, BufRead, Write},
+  ops::Range,
+  path::Path,
+  str::{CharIndices, FromStr},
+};
+
+// Converting a whole file of readings at once, one row at a time so the file never has to
+// fit in memory. A row that can't be converted is left out of the output and reported with
+// its line number, the rest of the file carries on.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+  // comma separated, with a header row naming the columns
+  Csv,
+  // one JSON object per line
+  JsonLines,
+}
+
+impl Format {
+  // .jsonl and .ndjson files are JSON lines, everything else is taken to be CSV. A .json file
+  // usually holds one big array rather than an object per line, so it's refused instead of
+  // failing on every row; `--format jsonl` still reads one that really is JSON lines.
+  pub fn from_path(path: &Path) -> Result<Format, BatchError> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+      Some("jsonl" | "ndjson") => Ok(Format::JsonLines),
+      Some("json") => Err(BatchError::PlainJson),
+      _ => Ok(Format::Csv),
+    }
+  }
+}
+
+impl FromStr for Format {
+  type Err = BatchError;
+
+  fn from_str(input: &str) -> Result<Format, BatchError> {
+    match input.trim().to_lowercase().as_str() {
+      "csv" => Ok(Format::Csv),
+      "jsonl" | "ndjson" => Ok(Format::JsonLines),
+      _ => Err(BatchError::UnknownFormat(input.trim().to_string())),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+  pub format: Format,
+  pub column: String,
+  // the scale of bare numbers like `21.5`; values that carry a unit ("21.5C") use their own
+  pub from: Option<Scale>,
+  pub to: Scale,
+}
+
+// Problems with the file as a whole, which stop the conversion.
+#[derive(Debug)]
+pub enum BatchError {
+  Io(io::Error),
+  MissingHeader,
+  MissingColumn(String),
+  UnknownFormat(String),
+  PlainJson,
+}
+
+impl fmt::Display for BatchError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      BatchError::Io(error) => write!(f, "{error}"),
+      BatchError::MissingHeader => write!(f, "the file is empty, a CSV file needs a header row"),
+      BatchError::MissingColumn(column) => write!(f, "the header has no `{column}` column"),
+      BatchError::UnknownFormat(format) => {
+        write!(f, "unknown format `{format}`, expected csv or jsonl")
+      }
+      BatchError::PlainJson => write!(
+        f,
+        "only JSON lines files are supported, with one object per line (.jsonl)"
+      ),
+    }
+  }
+}
+
+impl std::error::Error for BatchError {}
+
+impl From<io::Error> for BatchError {
+  fn from(error: io::Error) -> BatchError {
+    BatchError::Io(error)
+  }
+}
+
+// A row that was skipped, `line` counts from 1 like an editor does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+  pub line: usize,
+  pub message: String,
+}
+
+impl fmt::Display for RowError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "line {}: {}", self.line, self.message)
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+  pub converted: usize,
+  pub errors: Vec<RowError>,
+}
+
+pub fn convert_stream<R: BufRead, W: Write>(
+  input: R,
+  output: W,
+  options: &Options,
+) -> Result<Report, BatchError> {
+  match options.format {
+    Format::Csv => convert_csv(input, output, options),
+    Format::JsonLines => convert_json_lines(input, output, options),
+  }
+}
+
+// One reading, "21.5" or "21.5 C", converted and written back as a plain number.
+fn convert_value(raw: &str, options: &Options) -> Result<String, String> {
+  let raw = raw.trim();
+  if raw.is_empty() {
+    return Err(format!("the `{}` value is empty", options.column));
+  }
+  let temperature = match (raw.parse::<f64>(), options.from) {
+    (Ok(value), Some(scale)) => Temperature::new(value, scale),
+    (Ok(_), None) => {
+      return Err(format!(
+        "`{raw}` has no scale and no --from scale was given"
+      ))
+    }
+    (Err(_), _) => raw.parse(),
+  }
+  .map_err(|error| error.to_string())?;
+  Ok(display_value(temperature.to(options.to).value()))
+}
+
+fn convert_csv<R: BufRead, W: Write>(
+  input: R,
+  mut output: W,
+  options: &Options,
+) -> Result<Report, BatchError> {
+  let mut lines = input.lines();
+  let header = lines.next().ok_or(BatchError::MissingHeader)??;
+  let header = header.trim_end_matches('\r');
+  let columns = split_csv(header).map_err(|_| BatchError::MissingHeader)?;
+  let column = columns
+    .iter()
+    .position(|name| name.trim() == options.column)
+    .ok_or_else(|| BatchError::MissingColumn(options.column.clone()))?;
+  writeln!(output, "{header}")?;
+
+  let mut report = Report::default();
+  for (index, line) in lines.enumerate() {
+    let line = line?;
+    let line = line.trim_end_matches('\r');
+    if line.trim().is_empty() {
+      continue;
+    }
+
+    let converted = split_csv(line).and_then(|mut fields| {
+      if fields.len() != columns.len() {
+        return Err(format!(
+          "expected {} fields, found {}",
+          columns.len(),
+          fields.len()
+        ));
+      }
+      fields[column] = convert_value(&fields[column], options)?;
+      Ok(join_csv(&fields))
+    });
+    match converted {
+      Ok(row) => {
+        writeln!(output, "{row}")?;
+        report.converted += 1;
+      }
+      // the header was line 1
+      Err(message) => report.errors.push(RowError {
+        line: index + 2,
+        message,
+      }),
+    }
+  }
+
+  output.flush()?;
+  Ok(report)
+}
+
+// Splits a CSV row, honouring quoted fields with `""` for a literal quote. Quoted fields
+// can't span lines, since rows are read one line at a time.
+fn split_csv(line: &str) -> Result<Vec<String>, String> {
+  let mut fields = Vec::new();
+  let mut field = String::new();
+  let mut chars = line.chars().peekable();
+  let mut in_quotes = false;
+  let mut was_quoted = false;
+
+  while let Some(c) = chars.next() {
+    if in_quotes {
+      match c {
+        '"' if chars.peek() == Some(&'"') => {
+          chars.next();
+          field.push('"');
+        }
+        '"' => in_quotes = false,
+        c => field.push(c),
+      }
+      continue;
+    }
+    match c {
+      ',' => {
+        fields.push(std::mem::take(&mut field));
+        was_quoted = false;
+      }
+      '"' if field.is_empty() && !was_quoted => {
+        in_quotes = true;
+        was_quoted = true;
+      }
+      _ if was_quoted => return Err(String::from("unexpected text after a quoted field")),
+      '"' => return Err(String::from("stray quote in an unquoted field")),
+      c => field.push(c),
+    }
+  }
+
+  if in_quotes {
+    return Err(String::from("unterminated quoted field"));
+  }
+  fields.push(field);
+  Ok(fields)
+}
+
+fn join_csv(fields: &[String]) -> String {
+  fields
+    .iter()
+    .map(|field| {
+      if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+      } else {
+        field.clone()
+      }
+    })
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+fn convert_json_lines<R: BufRead, W: Write>(
+  input: R,
+  mut output: W,
+  options: &Options,
+) -> Result<Report, BatchError> {
+  let mut report = Report::default();
+
+  for (index, line) in input.lines().enumerate() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+
+    let converted = find_json_value(&line, &options.column).and_then(|range| {
+      let raw = &line[range.clone()];
+      let value = match raw.as_bytes()[0] {
+        b'"' => JsonScanner::new(raw).string()?,
+        _ if raw == "null" => return Err(format!("`{}` is null", options.column)),
+        _ => raw.to_string(),
+      };
+      let converted = convert_value(&value, options)?;
+      Ok(format!(
+        "{}{converted}{}",
+        &line[..range.start],
+        &line[range.end..]
+      ))
+    });
+    match converted {
+      Ok(row) => {
+        writeln!(output, "{row}")?;
+        report.converted += 1;
+      }
+      Err(message) => report.errors.push(RowError {
+        line: index + 1,
+        message,
+      }),
+    }
+  }
+
+  output.flush()?;
+  Ok(report)
+}
+
+// Where the value of a top-level `key` sits in a one-line JSON object. Everything else in
+// the object is only skipped over, so it is passed through exactly as it was written, but the
+// whole line is still checked so a truncated row doesn't slip through.
+fn find_json_value(line: &str, key: &str) -> Result<Range<usize>, String> {
+  let mut scanner = JsonScanner::new(line);
+  scanner
+    .expect(b'{')
+    .map_err(|_| String::from("not a JSON object"))?;
+  let mut found = None;
+
+  if !scanner.eat(b'}') {
+    loop {
+      let name = scanner.string()?;
+      scanner.expect(b':')?;
+      scanner.skip_whitespace();
+      let start = scanner.position;
+      scanner.skip_value()?;
+      if name == key && found.is_none() {
+        found = Some(start..scanner.position);
+      }
+      if scanner.eat(b'}') {
+        break;
+      }
+      scanner.expect(b',')?;
+    }
+  }
+
+  scanner.skip_whitespace();
+  if scanner.peek().is_some() {
+    return Err(format!(
+      "unexpected text after the object at column {}",
+      scanner.position + 1
+    ));
+  }
+  found.ok_or_else(|| format!("no `{key}` field"))
+}
+
+struct JsonScanner<'a> {
+  text: &'a str,
+  position: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+  fn new(text: &'a str) -> JsonScanner<'a> {
+    JsonScanner { text, position: 0 }
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.text.as_bytes().get(self.position).copied()
+  }
+
+  fn skip_whitespace(&mut self) {
+    while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+      self.position += 1;
+    }
+  }
+
+  fn eat(&mut self, expected: u8) -> bool {
+    self.skip_whitespace();
+    let found = self.peek() == Some(expected);
+    if found {
+      self.position += 1;
+    }
+    found
+  }
+
+  fn expect(&mut self, expected: u8) -> Result<(), String> {
+    if self.eat(expected) {
+      return Ok(());
+    }
+    Err(match self.peek() {
+      Some(_) => format!(
+        "expected `{}` at column {}",
+        expected as char,
+        self.position + 1
+      ),
+      None => format!("expected `{}` but the line ended", expected as char),
+    })
+  }
+
+  // Reads a string and decodes its escapes.
+  fn string(&mut self) -> Result<String, String> {
+    self.expect(b'"')?;
+    let mut decoded = String::new();
+    let mut chars = self.text[self.position..].char_indices();
+
+    while let Some((offset, c)) = chars.next() {
+      match c {
+        '"' => {
+          self.position += offset + 1;
+          return Ok(decoded);
+        }
+        '\\' => {
+          let escaped = match chars.next().map(|(_, c)| c) {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+              let high = unicode_escape(&mut chars)?;
+              // characters outside the basic plane are written as a surrogate pair,
+              // `\ud83c\udf21` for 🌡
+              let code = match high {
+                0xd800..=0xdbff => {
+                  let low = match (chars.next(), chars.next()) {
+                    (Some((_, '\\')), Some((_, 'u'))) => unicode_escape(&mut chars)?,
+                    _ => return Err(String::from("unpaired surrogate in a unicode escape")),
+                  };
+                  if !(0xdc00..=0xdfff).contains(&low) {
+                    return Err(String::from("unpaired surrogate in a unicode escape"));
+                  }
+                  0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                }
+                code => code,
+              };
+              char::from_u32(code)
+                .ok_or_else(|| String::from("unpaired surrogate in a unicode escape"))?
+            }
+            _ => return Err(String::from("bad escape in a string")),
+          };
+          decoded.push(escaped);
+        }
+        c => decoded.push(c),
+      }
+    }
+
+    Err(String::from("unterminated string"))
+  }
+
+  // Steps over a value of any kind without looking at what's inside it.
+  fn skip_value(&mut self) -> Result<(), String> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some(b'"') => self.string().map(|_| ()),
+      Some(b'{' | b'[') => {
+        let mut depth = 0;
+        loop {
+          match self.peek() {
+            Some(b'"') => {
+              self.string()?;
+              continue;
+            }
+            Some(b'{' | b'[') => depth += 1,
+            Some(b'}' | b']') => depth -= 1,
+            Some(_) => {}
+            None => return Err(String::from("unterminated object or array")),
+          }
+          self.position += 1;
+          if depth == 0 {
+            return Ok(());
+          }
+        }
+      }
+      // numbers, true, false and null all run until the next delimiter
+      Some(_) => {
+        let start = self.position;
+        while self
+          .peek()
+          .is_some_and(|byte| !matches!(byte, b',' | b'}' | b']') && !byte.is_ascii_whitespace())
+        {
+          self.position += 1;
+        }
+        match self.position > start {
+          true => Ok(()),
+          false => Err(format!("missing value at column {}", start + 1)),
+        }
+      }
+      None => Err(String::from("missing value at the end of the line")),
+    }
+  }
+}
+
+// The four hex digits after a `\u`.
+fn unicode_escape(chars: &mut CharIndices<'_>) -> Result<u32, String> {
+  let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+  if hex.len() != 4 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return Err(format!("bad unicode escape `\\u{hex}`"));
+  }
+  Ok(u32::from_str_radix(&hex, 16).expect("four hex digits fit in a u32"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn options(format: Format) -> Options {
+    Options {
+      format,
+      column: String::from("temp"),
+      from: Some(Scale::Fahrenheit),
+      to: Scale::Celsius,
+    }
+  }
+
+  fn convert(input: &str, options: &Options) -> (String, Report) {
+    let mut output = Vec::new();
+    let report = convert_stream(input.as_bytes(), &mut output, options).unwrap();
+    (String::from_utf8(output).unwrap(), report)
+  }
+
+  #[test]
+  fn converts_a_csv_column() {
+    let input = "\
+sensor,temp,note
+a,212,boiling
+b,32F,\"freezing, \"\"ice\"\"\"
+c,hot,oops
+d,-500,too cold
+\"e,1,x
+f,50
+g,\"98.6 °F\",fever
+";
+    let (output, report) = convert(input, &options(Format::Csv));
+
+    assert_eq!(
+      output,
+      "\
+sensor,temp,note
+a,100,boiling
+b,0,\"freezing, \"\"ice\"\"\"
+g,37,fever
+"
+    );
+    assert_eq!(report.converted, 3);
+    let lines: Vec<usize> = report.errors.iter().map(|error| error.line).collect();
+    assert_eq!(lines, [4, 5, 6, 7]);
+    assert_eq!(report.errors[3].message, "expected 3 fields, found 2");
+  }
+
+  #[test]
+  fn converts_json_lines_and_keeps_the_rest_of_the_object() {
+    let input = r#"{"sensor": "a", "temp": 212, "tags": ["x", {"temp": 1}]}
+{"temp": "300K", "sensor": "b\"c"}
+
+{"sensor": "c"}
+[1, 2]
+{"temp": null}
+{"temp": 50
+"#;
+    let (output, report) = convert(input, &options(Format::JsonLines));
+
+    assert_eq!(
+      output,
+      r#"{"sensor": "a", "temp": 100, "tags": ["x", {"temp": 1}]}
+{"temp": 26.85, "sensor": "b\"c"}
+"#
+    );
+    assert_eq!(report.converted, 2);
+    let errors: Vec<String> = report
+      .errors
+      .iter()
+      .map(|error| error.to_string())
+      .collect();
+    assert_eq!(
+      errors,
+      [
+        "line 4: no `temp` field",
+        "line 5: not a JSON object",
+        "line 6: `temp` is null",
+        "line 7: expected `,` but the line ended",
+      ]
+    );
+  }
+
+  #[test]
+  fn whole_file_problems_stop_the_conversion() {
+    let missing = convert_stream(
+      "sensor,temperature\n".as_bytes(),
+      io::sink(),
+      &options(Format::Csv),
+    );
+    assert!(matches!(missing, Err(BatchError::MissingColumn(column)) if column == "temp"));
+    assert!(matches!(
+      convert_stream("".as_bytes(), io::sink(), &options(Format::Csv)),
+      Err(BatchError::MissingHeader)
+    ));
+
+    assert!(matches!(
+      Format::from_path(Path::new("log.jsonl")),
+      Ok(Format::JsonLines)
+    ));
+    assert!(matches!(
+      Format::from_path(Path::new("log.csv")),
+      Ok(Format::Csv)
+    ));
+    assert!(matches!(
+      Format::from_path(Path::new("log.json")),
+      Err(BatchError::PlainJson)
+    ));
+    assert!("xml".parse::<Format>().is_err());
+  }
+
+  #[test]
+  fn decodes_surrogate_pairs_in_strings() {
+    assert_eq!(
+      JsonScanner::new(r#""\ud83c\udf21 \u00e9""#).string(),
+      Ok(String::from("🌡 é"))
+    );
+    assert!(JsonScanner::new(r#""\ud83c""#).string().is_err());
+    assert!(JsonScanner::new(r#""\udf21\ud83c""#).string().is_err());
+    assert!(JsonScanner::new(r#""\u00""#).string().is_err());
+  }
+
+  #[test]
+  fn bare_numbers_need_a_scale() {
+    let options = Options {
+      from: None,
+      ..options(Format::Csv)
+    };
+    let (output, report) = convert("temp\n20\n20C\n", &options);
+    assert_eq!(output, "temp\n20\n");
+    assert_eq!(report.errors[0].line, 2);
+  }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+use std::ops::{Add, Mul};
+
+// Each limb holds nine decimal digits, so printing the number is just printing the limbs
+const BASE: u64 = 1_000_000_000;
+
+// A tiny arbitrary-precision unsigned integer, just enough for big Fibonacci numbers.
+// Limbs are stored least significant first and there are never any trailing zero limbs.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigUint {
+  limbs: Vec<u32>,
+}
+
+impl BigUint {
+  pub fn zero() -> BigUint {
+    BigUint { limbs: Vec::new() }
+  }
+
+  pub fn one() -> BigUint {
+    BigUint::from(1u32)
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.limbs.is_empty()
+  }
+
+  fn trim(mut self) -> BigUint {
+    while self.limbs.last() == Some(&0) {
+      self.limbs.pop();
+    }
+    self
+  }
+}
+
+impl From<u32> for BigUint {
+  fn from(value: u32) -> BigUint {
+    BigUint::from(u128::from(value))
+  }
+}
+
+impl From<u64> for BigUint {
+  fn from(value: u64) -> BigUint {
+    BigUint::from(u128::from(value))
+  }
+}
+
+impl From<u128> for BigUint {
+  fn from(mut value: u128) -> BigUint {
+    let mut limbs = Vec::new();
+    while value > 0 {
+      limbs.push((value % BASE as u128) as u32);
+      value /= BASE as u128;
+    }
+    BigUint { limbs }
+  }
+}
+
+impl Add for &BigUint {
+  type Output = BigUint;
+
+  fn add(self, other: &BigUint) -> BigUint {
+    let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+    let mut carry = 0;
+
+    for i in 0..self.limbs.len().max(other.limbs.len()) {
+      let sum = carry
+        + u64::from(*self.limbs.get(i).unwrap_or(&0))
+        + u64::from(*other.limbs.get(i).unwrap_or(&0));
+      limbs.push((sum % BASE) as u32);
+      carry = sum / BASE;
+    }
+    if carry > 0 {
+      limbs.push(carry as u32);
+    }
+
+    BigUint { limbs }
+  }
+}
+
+impl Add for BigUint {
+  type Output = BigUint;
+
+  fn add(self, other: BigUint) -> BigUint {
+    &self + &other
+  }
+}
+
+impl Mul for &BigUint {
+  type Output = BigUint;
+
+  // plain schoolbook multiplication, plenty fast for a few thousand digits
+  fn mul(self, other: &BigUint) -> BigUint {
+    if self.is_zero() || other.is_zero() {
+      return BigUint::zero();
+    }
+    let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+
+    for (i, &a) in self.limbs.iter().enumerate() {
+      let mut carry = 0;
+      for (j, &b) in other.limbs.iter().enumerate() {
+        let current = limbs[i + j] + u64::from(a) * u64::from(b) + carry;
+        limbs[i + j] = current % BASE;
+        carry = current / BASE;
+      }
+      limbs[i + other.limbs.len()] += carry;
+    }
+
+    BigUint {
+      limbs: limbs.into_iter().map(|limb| limb as u32).collect(),
+    }
+    .trim()
+  }
+}
+
+impl Mul for BigUint {
+  type Output = BigUint;
+
+  fn mul(self, other: BigUint) -> BigUint {
+    &self * &other
+  }
+}
+
+impl fmt::Display for BigUint {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut limbs = self.limbs.iter().rev();
+    match limbs.next() {
+      Some(most_significant) => write!(f, "{most_significant}")?,
+      None => return write!(f, "0"),
+    }
+    // every limb after the first one has to be padded back to its nine digits
+    for limb in limbs {
+      write!(f, "{limb:09}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn adds_and_multiplies_across_limbs() {
+    let a = BigUint::from(999_999_999_999u64);
+    let b = BigUint::from(1u32);
+    assert_eq!((&a + &b).to_string(), "1000000000000");
+    assert_eq!((&a * &a).to_string(), "999999999998000000000001");
+    assert_eq!((&a * &BigUint::zero()).to_string(), "0");
+  }
+
+  #[test]
+  fn converts_from_u128() {
+    assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+    assert_eq!(BigUint::from(0u32), BigUint::zero());
+  }
+}
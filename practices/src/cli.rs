@@ -0,0 +1,406 @@
+use crate::{
+  batch::{self, Format},
+  fibonacci::{fibonacci_fast, fibonacci_mod},
+  temperature_converter::{display_value, Scale, Temperature},
+};
+use std::{
+  io::{self, BufRead, Write},
+  num::{IntErrorKind, ParseIntError},
+  path::PathBuf,
+};
+
+pub const USAGE: &str = "\
+Usage: practices [COMMAND]
+
+Commands:
+    temp <TEMPERATURE> [--to <SCALE>]  convert a temperature like 98.6F, to every scale by default
+    fib <N> [--mod <M>]                print the Nth fibonacci number, F(0) = 0, optionally modulo M,
+                                       N can be at most 1000000 without `--mod`
+    batch <FILE> --column <NAME> --to <SCALE> [--from <SCALE>] [--output <PATH>] [--format <csv|jsonl>]
+                                       convert a column of a CSV or JSON lines file, printing
+                                       malformed rows to stderr instead of stopping at them
+    repl                               read commands from stdin, one per line (the default)
+    help                               print this message
+
+Scales are C, F, K, R (Rankine) and Ré (Réaumur).
+Exits with 2 when the command line can't be understood and 1 when the input is invalid,
+including when `batch` had to skip rows.";
+
+pub const PROMPT: &str = "> ";
+// F(1000000) has over 200000 digits and takes a couple of seconds, much past that `fib` would
+// seem to hang. Modulo something it stays quick for any N.
+pub const MAX_FIB_INDEX: i64 = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+  Help,
+  Repl,
+  Temp {
+    temperature: Temperature,
+    to: Option<Scale>,
+  },
+  Fib {
+    n: i64,
+    modulus: Option<u64>,
+  },
+  Batch {
+    input: PathBuf,
+    output: Option<PathBuf>,
+    options: batch::Options,
+  },
+}
+
+// Why a command failed, which decides the exit code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+  // the arguments don't make a command
+  Usage(String),
+  // the command made sense but its input didn't
+  Invalid(String),
+}
+
+impl CliError {
+  pub fn exit_code(&self) -> i32 {
+    match self {
+      CliError::Usage(_) => 2,
+      CliError::Invalid(_) => 1,
+    }
+  }
+
+  pub fn message(&self) -> &str {
+    match self {
+      CliError::Usage(message) | CliError::Invalid(message) => message,
+    }
+  }
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, CliError>
+where
+  I: IntoIterator<Item = String>,
+{
+  let mut args = args.into_iter();
+  let command = match args.next() {
+    Some(command) => command,
+    None => return Ok(Command::Repl),
+  };
+
+  match command.as_str() {
+    "-h" | "--help" | "help" => Ok(Command::Help),
+    "repl" => Ok(Command::Repl),
+    "temp" => parse_temp(args),
+    "fib" => parse_fib(args),
+    "batch" => parse_batch(args),
+    other => Err(CliError::Usage(format!("unknown command `{other}`"))),
+  }
+}
+
+// The number and its scale can come as one argument ("98.6F") or two ("98.6 F").
+fn parse_temp<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+  let mut words = Vec::new();
+  let mut to = None;
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--to" | "to" => {
+        let scale = value(&arg, &mut args)?;
+        to = Some(
+          scale
+            .parse()
+            .map_err(|error| CliError::Invalid(format!("{error}")))?,
+        );
+      }
+      _ => words.push(arg),
+    }
+  }
+
+  if words.is_empty() {
+    return Err(CliError::Usage(String::from(
+      "`temp` expects a temperature, like 98.6F",
+    )));
+  }
+  let temperature = words
+    .join(" ")
+    .parse()
+    .map_err(|error| CliError::Invalid(format!("{error}")))?;
+  Ok(Command::Temp { temperature, to })
+}
+
+fn parse_fib<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+  let mut n = None;
+  let mut modulus = None;
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--mod" => {
+        let raw = value(&arg, &mut args)?;
+        modulus = Some(raw.trim().parse().map_err(|_| {
+          CliError::Invalid(format!("`--mod` expects a positive number, got `{raw}`"))
+        })?);
+      }
+      raw if n.is_none() => {
+        n = Some(raw.trim().parse().map_err(|error: ParseIntError| {
+          CliError::Invalid(match error.kind() {
+            IntErrorKind::PosOverflow => format!("`{raw}` is too big for an index"),
+            _ => format!("`{raw}` is not a whole number"),
+          })
+        })?)
+      }
+      other => return Err(CliError::Usage(format!("unexpected argument `{other}`"))),
+    }
+  }
+
+  match n {
+    Some(n) if n > MAX_FIB_INDEX && modulus.is_none() => Err(CliError::Invalid(format!(
+      "F({n}) would take too long, the largest index is {MAX_FIB_INDEX} unless it's with `--mod`"
+    ))),
+    Some(n) => Ok(Command::Fib { n, modulus }),
+    None => Err(CliError::Usage(String::from(
+      "`fib` expects an index, like 90",
+    ))),
+  }
+}
+
+fn parse_batch<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+  let mut input = None;
+  let mut output = None;
+  let mut column = None;
+  let mut from = None;
+  let mut to = None;
+  let mut format = None;
+  let scale = |raw: String| {
+    raw
+      .parse::<Scale>()
+      .map_err(|error| CliError::Invalid(error.to_string()))
+  };
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--column" => column = Some(value(&arg, &mut args)?),
+      "--from" => from = Some(scale(value(&arg, &mut args)?)?),
+      "--to" => to = Some(scale(value(&arg, &mut args)?)?),
+      "--output" => output = Some(PathBuf::from(value(&arg, &mut args)?)),
+      "--format" => {
+        format = Some(
+          value(&arg, &mut args)?
+            .parse::<Format>()
+            .map_err(|error| CliError::Usage(error.to_string()))?,
+        )
+      }
+      other if other.starts_with("--") => {
+        return Err(CliError::Usage(format!("unknown argument `{other}`")))
+      }
+      _ if input.is_none() => input = Some(PathBuf::from(arg)),
+      other => return Err(CliError::Usage(format!("unexpected argument `{other}`"))),
+    }
+  }
+
+  let input = input.ok_or_else(|| CliError::Usage(String::from("`batch` expects a file")))?;
+  let column = column.ok_or_else(|| CliError::Usage(String::from("`batch` needs --column")))?;
+  let to = to.ok_or_else(|| CliError::Usage(String::from("`batch` needs --to")))?;
+  let format = match format {
+    Some(format) => format,
+    None => Format::from_path(&input).map_err(|error| CliError::Usage(error.to_string()))?,
+  };
+  let options = batch::Options {
+    format,
+    column,
+    from,
+    to,
+  };
+  Ok(Command::Batch {
+    input,
+    output,
+    options,
+  })
+}
+
+fn value<I: Iterator<Item = String>>(flag: &str, args: &mut I) -> Result<String, CliError> {
+  args
+    .next()
+    .ok_or_else(|| CliError::Usage(format!("`{flag}` expects a value")))
+}
+
+// Runs a one-shot command and returns what it prints. `Repl` needs stdin, see `repl`.
+pub fn evaluate(command: &Command) -> Result<String, CliError> {
+  match command {
+    Command::Help | Command::Repl => Ok(String::from(USAGE)),
+    Command::Batch { .. } => Err(CliError::Usage(String::from(
+      "`batch` works on files, run it from the command line",
+    ))),
+    Command::Temp {
+      temperature,
+      to: Some(scale),
+    } => Ok(format!("{temperature} = {}", rounded(temperature.to(*scale)))),
+    Command::Temp {
+      temperature,
+      to: None,
+    } => Ok(
+      Scale::ALL
+        .iter()
+        .filter(|&&scale| scale != temperature.scale())
+        .map(|&scale| format!("{temperature} = {}", rounded(temperature.to(scale))))
+        .collect::<Vec<_>>()
+        .join("\n"),
+    ),
+    Command::Fib { n, modulus: None } => {
+      let value = fibonacci_fast(*n).map_err(|error| CliError::Invalid(error.to_string()))?;
+      Ok(format!("F({n}) = {value}"))
+    }
+    Command::Fib {
+      n,
+      modulus: Some(modulus),
+    } => {
+      let value =
+        fibonacci_mod(*n, *modulus).map_err(|error| CliError::Invalid(error.to_string()))?;
+      Ok(format!("F({n}) mod {modulus} = {value}"))
+    }
+  }
+}
+
+fn rounded(temperature: Temperature) -> String {
+  format!(
+    "{} {}",
+    display_value(temperature.value()),
+    temperature.scale().symbol()
+  )
+}
+
+// Reads one command per line until the input ends or someone types `quit`. Mistakes are
+// printed and the loop carries on, the number of them is returned.
+pub fn repl<R: BufRead, W: Write>(input: R, mut output: W) -> io::Result<usize> {
+  let mut errors = 0;
+  write!(output, "{PROMPT}")?;
+  output.flush()?;
+
+  for line in input.lines() {
+    let line = line?;
+    let words: Vec<String> = line.split_whitespace().map(String::from).collect();
+
+    match words.first().map(String::as_str) {
+      None => {}
+      Some("quit" | "exit") => break,
+      Some(_) => match parse_args(words).and_then(|command| match command {
+        Command::Repl => Err(CliError::Usage(String::from("already in the repl"))),
+        command => evaluate(&command),
+      }) {
+        Ok(text) => writeln!(output, "{text}")?,
+        Err(error) => {
+          errors += 1;
+          writeln!(output, "error: {}", error.message())?;
+        }
+      },
+    }
+
+    write!(output, "{PROMPT}")?;
+    output.flush()?;
+  }
+
+  writeln!(output)?;
+  Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+  }
+
+  fn run(line: &str) -> Result<String, CliError> {
+    parse_args(args(line)).and_then(|command| evaluate(&command))
+  }
+
+  #[test]
+  fn parses_subcommands() {
+    assert_eq!(
+      parse_args(args("temp 98.6 F --to C")),
+      Ok(Command::Temp {
+        temperature: Temperature::new(98.6, Scale::Fahrenheit).unwrap(),
+        to: Some(Scale::Celsius),
+      })
+    );
+    assert_eq!(
+      parse_args(args("fib 90 --mod 7")),
+      Ok(Command::Fib {
+        n: 90,
+        modulus: Some(7)
+      })
+    );
+    assert_eq!(
+      parse_args(args("batch log.jsonl --column t --to K --output out.jsonl")),
+      Ok(Command::Batch {
+        input: PathBuf::from("log.jsonl"),
+        output: Some(PathBuf::from("out.jsonl")),
+        options: batch::Options {
+          format: Format::JsonLines,
+          column: String::from("t"),
+          from: None,
+          to: Scale::Kelvin,
+        },
+      })
+    );
+    assert_eq!(parse_args(args("")), Ok(Command::Repl));
+    assert_eq!(parse_args(args("--help")), Ok(Command::Help));
+  }
+
+  #[test]
+  fn evaluates_commands() {
+    assert_eq!(run("temp 100C --to F"), Ok(String::from("100 °C = 212 °F")));
+    assert_eq!(
+      run("temp 0K").unwrap().lines().count(),
+      Scale::ALL.len() - 1
+    );
+    assert_eq!(
+      run("fib 90"),
+      Ok(String::from("F(90) = 2880067194370816120"))
+    );
+    assert_eq!(
+      run("fib 1000000 --mod 10"),
+      Ok(String::from("F(1000000) mod 10 = 5"))
+    );
+    // the limit is only on exact values
+    assert_eq!(
+      run("fib 1000000000000 --mod 10"),
+      Ok(String::from("F(1000000000000) mod 10 = 5"))
+    );
+  }
+
+  #[test]
+  fn bad_input_has_the_right_exit_code() {
+    assert_eq!(run("bake").unwrap_err().exit_code(), 2);
+    assert_eq!(run("temp").unwrap_err().exit_code(), 2);
+    assert_eq!(run("fib").unwrap_err().exit_code(), 2);
+    assert_eq!(run("batch log.csv --to C").unwrap_err().exit_code(), 2);
+    assert_eq!(
+      run("batch log.json --column t --to C")
+        .unwrap_err()
+        .exit_code(),
+      2
+    );
+    assert_eq!(run("temp -500C").unwrap_err().exit_code(), 1);
+    assert_eq!(run("temp 20C --to X").unwrap_err().exit_code(), 1);
+    assert_eq!(run("fib -3").unwrap_err().exit_code(), 1);
+    assert_eq!(run("fib 3 --mod 0").unwrap_err().exit_code(), 1);
+    assert_eq!(run("fib 1000001").unwrap_err().exit_code(), 1);
+    assert_eq!(run("fib 18446744073709551615").unwrap_err().exit_code(), 1);
+  }
+
+  #[test]
+  fn repl_keeps_going_after_errors() {
+    let mut output = Vec::new();
+    let errors = repl(
+      "fib 10\n\ntemp hot\nfib 2000000\ntemp 32F to C\nquit\nfib 11\n".as_bytes(),
+      &mut output,
+    )
+    .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(errors, 2);
+    assert!(output.contains("F(10) = 55\n"));
+    assert!(output.contains("error: `hot` is not a number\n"));
+    assert!(output.contains("error: F(2000000) would take too long"));
+    assert!(output.contains("32 °F = 0 °C\n"));
+    assert!(!output.contains("F(11)"));
+  }
+}
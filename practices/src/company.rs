@@ -0,0 +1,292 @@
+use std::{
+  collections::HashMap,
+  fmt,
+  io::{self, BufRead, Write},
+};
+
+// The Book's employee directory: people are added to departments with plain sentences like
+// "Add Sally to Engineering", and can be listed per department or for the whole company.
+//
+//   Add <name> to <department>
+//   Remove <name> from <department>
+//   List <department>
+//   List all
+//
+// Names and departments can be several words long; "to" and "from" split them, so a name
+// can't contain those words on their own.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+  Add { name: String, department: String },
+  Remove { name: String, department: String },
+  List { department: String },
+  ListAll,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectoryError {
+  Empty,
+  UnknownCommand(String),
+  // the command word was fine but the rest of the sentence wasn't, holds the expected shape
+  Malformed(&'static str),
+  AlreadyThere { name: String, department: String },
+  NotThere { name: String, department: String },
+  NoSuchDepartment(String),
+}
+
+impl fmt::Display for DirectoryError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DirectoryError::Empty => write!(f, "type a command, like `Add Sally to Engineering`"),
+      DirectoryError::UnknownCommand(command) => write!(
+        f,
+        "unknown command `{command}`, expected Add, Remove or List"
+      ),
+      DirectoryError::Malformed(usage) => write!(f, "expected `{usage}`"),
+      DirectoryError::AlreadyThere { name, department } => {
+        write!(f, "{name} is already in {department}")
+      }
+      DirectoryError::NotThere { name, department } => {
+        write!(f, "{name} isn't in {department}")
+      }
+      DirectoryError::NoSuchDepartment(department) => {
+        write!(f, "there's no {department} department")
+      }
+    }
+  }
+}
+
+impl std::error::Error for DirectoryError {}
+
+impl Command {
+  // The command word is case-insensitive, names and departments keep their case.
+  pub fn parse(line: &str) -> Result<Command, DirectoryError> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (command, rest) = match words.split_first() {
+      Some((command, rest)) => (command.to_lowercase(), rest),
+      None => return Err(DirectoryError::Empty),
+    };
+
+    match command.as_str() {
+      "add" => {
+        let (name, department) =
+          split_on(rest, "to").ok_or(DirectoryError::Malformed("Add <name> to <department>"))?;
+        Ok(Command::Add { name, department })
+      }
+      "remove" => {
+        let (name, department) = split_on(rest, "from")
+          .ok_or(DirectoryError::Malformed("Remove <name> from <department>"))?;
+        Ok(Command::Remove { name, department })
+      }
+      "list" => match rest {
+        [] => Ok(Command::ListAll),
+        [all] if all.eq_ignore_ascii_case("all") => Ok(Command::ListAll),
+        department => Ok(Command::List {
+          department: department.join(" "),
+        }),
+      },
+      _ => Err(DirectoryError::UnknownCommand(words[0].to_string())),
+    }
+  }
+}
+
+// Splits ["Sally", "Ann", "to", "Sales"] on "to" into ("Sally Ann", "Sales"), both non-empty.
+fn split_on(words: &[&str], separator: &str) -> Option<(String, String)> {
+  let at = words
+    .iter()
+    .position(|word| word.eq_ignore_ascii_case(separator))?;
+  let (before, after) = (&words[..at], &words[at + 1..]);
+  if before.is_empty() || after.is_empty() {
+    return None;
+  }
+  Some((before.join(" "), after.join(" ")))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Directory {
+  departments: HashMap<String, Vec<String>>,
+}
+
+impl Directory {
+  pub fn new() -> Directory {
+    Directory::default()
+  }
+
+  pub fn add(&mut self, name: &str, department: &str) -> Result<(), DirectoryError> {
+    let people = self.departments.entry(department.to_string()).or_default();
+    if people.iter().any(|person| person == name) {
+      return Err(DirectoryError::AlreadyThere {
+        name: name.to_string(),
+        department: department.to_string(),
+      });
+    }
+    people.push(name.to_string());
+    Ok(())
+  }
+
+  // Removing the last person from a department removes the department too.
+  pub fn remove(&mut self, name: &str, department: &str) -> Result<(), DirectoryError> {
+    let not_there = || DirectoryError::NotThere {
+      name: name.to_string(),
+      department: department.to_string(),
+    };
+    let people = self.departments.get_mut(department).ok_or_else(not_there)?;
+    let index = people
+      .iter()
+      .position(|person| person == name)
+      .ok_or_else(not_there)?;
+    people.remove(index);
+    if people.is_empty() {
+      self.departments.remove(department);
+    }
+    Ok(())
+  }
+
+  // Everyone in a department, sorted alphabetically.
+  pub fn people_in(&self, department: &str) -> Vec<&str> {
+    let mut people: Vec<&str> = self
+      .departments
+      .get(department)
+      .map(|people| people.iter().map(String::as_str).collect())
+      .unwrap_or_default();
+    people.sort_unstable();
+    people
+  }
+
+  // Every department with its people, departments and people both sorted alphabetically.
+  pub fn by_department(&self) -> Vec<(&str, Vec<&str>)> {
+    let mut departments: Vec<&str> = self.departments.keys().map(String::as_str).collect();
+    departments.sort_unstable();
+    departments
+      .into_iter()
+      .map(|department| (department, self.people_in(department)))
+      .collect()
+  }
+
+  // Runs one text command and returns what to show for it.
+  pub fn execute(&mut self, line: &str) -> Result<String, DirectoryError> {
+    match Command::parse(line)? {
+      Command::Add { name, department } => {
+        self.add(&name, &department)?;
+        Ok(format!("Added {name} to {department}."))
+      }
+      Command::Remove { name, department } => {
+        self.remove(&name, &department)?;
+        Ok(format!("Removed {name} from {department}."))
+      }
+      Command::List { department } => {
+        let people = self.people_in(&department);
+        if people.is_empty() {
+          return Err(DirectoryError::NoSuchDepartment(department));
+        }
+        Ok(format!("{department}: {}", people.join(", ")))
+      }
+      Command::ListAll if self.departments.is_empty() => Ok(String::from("Nobody works here yet.")),
+      Command::ListAll => Ok(
+        self
+          .by_department()
+          .into_iter()
+          .map(|(department, people)| format!("{department}: {}", people.join(", ")))
+          .collect::<Vec<_>>()
+          .join("\n"),
+      ),
+    }
+  }
+}
+
+// Reads commands one per line and prints what each one did, mistakes included.
+pub fn run_commands<R: BufRead, W: Write>(
+  directory: &mut Directory,
+  input: R,
+  mut output: W,
+) -> io::Result<()> {
+  for line in input.lines() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    match directory.execute(&line) {
+      Ok(text) => writeln!(output, "{text}")?,
+      Err(error) => writeln!(output, "error: {error}")?,
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_commands() {
+    assert_eq!(
+      Command::parse("Add Sally to Engineering"),
+      Ok(Command::Add {
+        name: String::from("Sally"),
+        department: String::from("Engineering")
+      })
+    );
+    assert_eq!(
+      Command::parse("remove Amir Khan FROM Research and Development"),
+      Ok(Command::Remove {
+        name: String::from("Amir Khan"),
+        department: String::from("Research and Development")
+      })
+    );
+    assert_eq!(Command::parse("list"), Ok(Command::ListAll));
+    assert_eq!(Command::parse("List all"), Ok(Command::ListAll));
+    assert_eq!(
+      Command::parse("Add Sally"),
+      Err(DirectoryError::Malformed("Add <name> to <department>"))
+    );
+    assert_eq!(
+      Command::parse("Fire Bob"),
+      Err(DirectoryError::UnknownCommand(String::from("Fire")))
+    );
+    assert_eq!(Command::parse("   "), Err(DirectoryError::Empty));
+  }
+
+  #[test]
+  fn lists_people_sorted_by_department() {
+    let mut directory = Directory::new();
+    for line in [
+      "Add Sally to Engineering",
+      "Add Amir to Sales",
+      "Add Bob to Engineering",
+      "Add Zoe to Accounting",
+    ] {
+      directory.execute(line).unwrap();
+    }
+
+    assert_eq!(directory.people_in("Engineering"), ["Bob", "Sally"]);
+    assert_eq!(
+      directory.execute("List all"),
+      Ok(String::from(
+        "Accounting: Zoe\nEngineering: Bob, Sally\nSales: Amir"
+      ))
+    );
+    assert_eq!(
+      directory.execute("List Engineering"),
+      Ok(String::from("Engineering: Bob, Sally"))
+    );
+  }
+
+  #[test]
+  fn reports_mistakes_and_keeps_going() {
+    let mut directory = Directory::new();
+    let input = "Add Sally to Engineering\nAdd Sally to Engineering\nRemove Bob from Sales\n\
+                 Remove Sally from Engineering\nList Engineering\nList\n";
+    let mut output = Vec::new();
+    run_commands(&mut directory, input.as_bytes(), &mut output).unwrap();
+
+    assert_eq!(
+      String::from_utf8(output).unwrap(),
+      "Added Sally to Engineering.\n\
+       error: Sally is already in Engineering\n\
+       error: Bob isn't in Sales\n\
+       Removed Sally from Engineering.\n\
+       error: there's no Engineering department\n\
+       Nobody works here yet.\n"
+    );
+  }
+}
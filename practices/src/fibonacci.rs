@@ -1,3 +1,10 @@
+use crate::big_uint::BigUint;
+use std::fmt;
+
+// Note that this one counts from 1, so the 1st fibonacci number is 0 and the 20th is F(19).
+// It overflows past the 47th and returns 0 for anything below 1, the functions further down
+// use the usual F(0) = 0, F(1) = 1 numbering and report those cases instead.
+#[allow(clippy::assign_op_pattern, clippy::needless_return)]
 pub fn generate_nth_fibonacci_number(nth: i32) -> i32 {
   let mut x = 0;
   let mut y = 1;
@@ -10,3 +17,336 @@ pub fn generate_nth_fibonacci_number(nth: i32) -> i32 {
 
   return x;
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FibonacciError {
+  NegativeIndex(i64),
+  // F(n) doesn't fit in the requested type, `max_index` is the largest n that does
+  Overflow { n: i64, max_index: i64 },
+  ZeroModulus,
+  // the pisano period is only searched for up to `max`, bigger moduli would take too long
+  ModulusTooLarge { m: u64, max: u64 },
+}
+
+impl fmt::Display for FibonacciError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      FibonacciError::NegativeIndex(n) => {
+        write!(f, "fibonacci numbers start at F(0), F({n}) doesn't exist")
+      }
+      FibonacciError::Overflow { n, max_index } => write!(
+        f,
+        "F({n}) is too big for this type, the largest one it can hold is F({max_index})"
+      ),
+      FibonacciError::ZeroModulus => write!(f, "can't take fibonacci numbers modulo 0"),
+      FibonacciError::ModulusTooLarge { m, max } => write!(
+        f,
+        "the pisano period of {m} would take too long to find, the limit is {max}"
+      ),
+    }
+  }
+}
+
+impl std::error::Error for FibonacciError {}
+
+fn check_index(n: i64) -> Result<u64, FibonacciError> {
+  u64::try_from(n).map_err(|_| FibonacciError::NegativeIndex(n))
+}
+
+// F(n) as an i32, which works up to F(46).
+pub fn checked_fibonacci(n: i32) -> Result<i32, FibonacciError> {
+  let value = fibonacci_u128(i64::from(n))?;
+  i32::try_from(value).map_err(|_| FibonacciError::Overflow {
+    n: i64::from(n),
+    max_index: 46,
+  })
+}
+
+// F(n) as a u128, which works up to F(186).
+pub fn fibonacci_u128(n: i64) -> Result<u128, FibonacciError> {
+  let steps = check_index(n)?;
+  let mut x: u128 = 0;
+  // `y` runs one step ahead, so it's `None` once it overflowed but only matters if we need it
+  let mut y = Some(1u128);
+
+  for _ in 0..steps {
+    let current = y.ok_or(FibonacciError::Overflow { n, max_index: 186 })?;
+    y = x.checked_add(current);
+    x = current;
+  }
+
+  Ok(x)
+}
+
+// F(n) with as many digits as it takes, F(10_000) has 2090 of them.
+pub fn fibonacci_big(n: i64) -> Result<BigUint, FibonacciError> {
+  let steps = check_index(n)?;
+  let mut x = BigUint::zero();
+  let mut y = BigUint::one();
+
+  for _ in 0..steps {
+    let next = &x + &y;
+    x = y;
+    y = next;
+  }
+
+  Ok(x)
+}
+
+// Fast doubling: from (F(k-1), F(k)) we can jump straight to
+//   F(2k-1) = F(k)^2 + F(k-1)^2
+//   F(2k)   = F(k) * (F(k) + 2 * F(k-1))
+// so walking the bits of n takes O(log n) steps instead of n additions.
+pub fn fibonacci_fast(n: i64) -> Result<BigUint, FibonacciError> {
+  let n = check_index(n)?;
+  // (F(-1), F(0)), with F(-1) = 1 keeping the identities above true for k = 0
+  let mut previous = BigUint::one();
+  let mut current = BigUint::zero();
+
+  for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+    let doubled_previous = &(&current * &current) + &(&previous * &previous);
+    let doubled_current = &current * &(&current + &(&previous + &previous));
+    previous = doubled_previous;
+    current = doubled_current;
+
+    if n >> bit & 1 == 1 {
+      let next = &previous + &current;
+      previous = current;
+      current = next;
+    }
+  }
+
+  Ok(current)
+}
+
+// F(n) mod m, using the same fast doubling with every step reduced modulo m.
+pub fn fibonacci_mod(n: i64, m: u64) -> Result<u64, FibonacciError> {
+  let n = check_index(n)?;
+  if m == 0 {
+    return Err(FibonacciError::ZeroModulus);
+  }
+  let m = u128::from(m);
+  let mut previous = 1 % m;
+  let mut current = 0;
+
+  for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+    // both squares are below m^2 < 2^128, but their sum might not be
+    let doubled_previous = (current * current % m + previous * previous % m) % m;
+    let doubled_current = current * ((current + 2 * previous) % m) % m;
+    previous = doubled_previous;
+    current = doubled_current;
+
+    if n >> bit & 1 == 1 {
+      let next = (previous + current) % m;
+      previous = current;
+      current = next;
+    }
+  }
+
+  Ok(current as u64)
+}
+
+// Finding the period takes up to 6m steps, past this it's refused instead of looping for ages.
+pub const PISANO_MAX_MODULUS: u64 = 10_000_000;
+
+// The Pisano period: fibonacci numbers modulo m repeat with this period, so
+// F(n) mod m == F(n mod pisano_period(m)) mod m. It is never more than 6m, and finding it
+// takes that many steps, so it's worth it when the same m is used for lots of indices.
+pub fn pisano_period(m: u64) -> Result<u64, FibonacciError> {
+  if m == 0 {
+    return Err(FibonacciError::ZeroModulus);
+  }
+  if m > PISANO_MAX_MODULUS {
+    return Err(FibonacciError::ModulusTooLarge {
+      m,
+      max: PISANO_MAX_MODULUS,
+    });
+  }
+  if m == 1 {
+    return Ok(1);
+  }
+  let m = u128::from(m);
+  let (mut x, mut y) = (0, 1);
+  let mut period = 0;
+
+  loop {
+    (x, y) = (y, (x + y) % m);
+    period += 1;
+    if (x, y) == (0, 1) {
+      return Ok(period);
+    }
+  }
+}
+
+// Iterates F(0), F(1), F(2), ... and ends after F(93), the last one that fits in a u64,
+// instead of overflowing.
+#[derive(Debug, Clone)]
+pub struct Fibonacci {
+  current: Option<u64>,
+  next: Option<u64>,
+}
+
+impl Fibonacci {
+  pub fn new() -> Fibonacci {
+    Fibonacci {
+      current: Some(0),
+      next: Some(1),
+    }
+  }
+}
+
+impl Default for Fibonacci {
+  fn default() -> Fibonacci {
+    Fibonacci::new()
+  }
+}
+
+impl Iterator for Fibonacci {
+  type Item = u64;
+
+  fn next(&mut self) -> Option<u64> {
+    let current = self.current?;
+    let after = self.next.and_then(|next| next.checked_add(current));
+    self.current = self.next;
+    self.next = after;
+    Some(current)
+  }
+}
+
+pub fn is_fibonacci(n: u64) -> bool {
+  fibonacci_index_of(n).is_some()
+}
+
+// The smallest i with F(i) == n. 1 shows up twice, as F(1) and F(2), so it gives 1.
+pub fn fibonacci_index_of(n: u64) -> Option<u64> {
+  Fibonacci::new()
+    .take_while(|&value| value <= n)
+    .position(|value| value == n)
+    .map(|index| index as u64)
+}
+
+// Every positive integer is a sum of non-consecutive fibonacci numbers in exactly one way
+// (Zeckendorf's theorem). Greedily taking the largest one that still fits finds it.
+// The terms come back largest first, and 0 is the empty sum.
+pub fn zeckendorf(mut n: u64) -> Vec<u64> {
+  // skip F(0) and F(1), the representation only uses F(2) = 1 and up
+  let candidates: Vec<u64> = Fibonacci::new().skip(2).take_while(|&value| value <= n).collect();
+  let mut terms = Vec::new();
+
+  for &value in candidates.iter().rev() {
+    if value <= n {
+      terms.push(value);
+      n -= value;
+    }
+  }
+
+  terms
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn original_function_counts_from_one() {
+    assert_eq!(generate_nth_fibonacci_number(1), 0);
+    assert_eq!(generate_nth_fibonacci_number(20), 4181);
+    assert_eq!(checked_fibonacci(19), Ok(4181));
+  }
+
+  #[test]
+  fn checked_variants_stop_at_their_limits() {
+    assert_eq!(checked_fibonacci(46), Ok(1_836_311_903));
+    assert_eq!(
+      checked_fibonacci(47),
+      Err(FibonacciError::Overflow { n: 47, max_index: 46 })
+    );
+    assert_eq!(
+      fibonacci_u128(186),
+      Ok(332_825_110_087_067_562_321_196_029_789_634_457_848)
+    );
+    assert!(fibonacci_u128(187).is_err());
+  }
+
+  #[test]
+  fn negative_indices_are_errors() {
+    assert_eq!(checked_fibonacci(-1), Err(FibonacciError::NegativeIndex(-1)));
+    assert_eq!(fibonacci_big(-5), Err(FibonacciError::NegativeIndex(-5)));
+  }
+
+  #[test]
+  fn big_variant_is_exact() {
+    assert_eq!(fibonacci_big(0).unwrap().to_string(), "0");
+    assert_eq!(
+      fibonacci_big(186).unwrap().to_string(),
+      fibonacci_u128(186).unwrap().to_string()
+    );
+
+    let f10000 = fibonacci_big(10_000).unwrap().to_string();
+    assert_eq!(f10000.len(), 2090);
+    assert!(f10000.starts_with("33644764876431783266"));
+    assert!(f10000.ends_with("66073310059947366875"));
+  }
+
+  #[test]
+  fn iterator_stops_before_overflowing() {
+    let first: Vec<u64> = Fibonacci::new().take(8).collect();
+    assert_eq!(first, [0, 1, 1, 2, 3, 5, 8, 13]);
+    assert_eq!(Fibonacci::new().count(), 94);
+    assert_eq!(Fibonacci::new().last(), Some(12_200_160_415_121_876_738));
+  }
+
+  #[test]
+  fn finds_fibonacci_numbers_and_their_indices() {
+    assert!(is_fibonacci(0));
+    assert!(is_fibonacci(144));
+    assert!(!is_fibonacci(4));
+    assert_eq!(fibonacci_index_of(1), Some(1));
+    assert_eq!(fibonacci_index_of(6_765), Some(20));
+    assert_eq!(fibonacci_index_of(12_200_160_415_121_876_738), Some(93));
+    assert_eq!(fibonacci_index_of(u64::MAX), None);
+  }
+
+  #[test]
+  fn zeckendorf_representations() {
+    assert_eq!(zeckendorf(0), Vec::<u64>::new());
+    assert_eq!(zeckendorf(64), [55, 8, 1]);
+    assert_eq!(zeckendorf(100), [89, 8, 3]);
+    for n in 1..500 {
+      let terms = zeckendorf(n);
+      assert_eq!(terms.iter().sum::<u64>(), n);
+      assert!(terms.iter().all(|&term| is_fibonacci(term)));
+    }
+  }
+
+  #[test]
+  fn fast_doubling_matches_the_iterative_version() {
+    for n in [0, 1, 2, 3, 10, 63, 64, 100, 1_000, 4_321] {
+      assert_eq!(fibonacci_fast(n), fibonacci_big(n), "F({n})");
+    }
+  }
+
+  #[test]
+  fn modular_fibonacci_repeats_with_the_pisano_period() {
+    assert_eq!(pisano_period(10), Ok(60));
+    assert_eq!(pisano_period(1_000), Ok(1_500));
+    let f186 = fibonacci_u128(186).unwrap();
+    assert_eq!(
+      fibonacci_mod(186, u64::MAX),
+      Ok((f186 % u128::from(u64::MAX)) as u64)
+    );
+
+    let period = pisano_period(1_000).unwrap() as i64;
+    let huge = 1_000_000_000_000_000;
+    assert_eq!(fibonacci_mod(huge, 1_000), fibonacci_mod(huge % period, 1_000));
+    assert_eq!(fibonacci_mod(10, 1), Ok(0));
+    assert_eq!(fibonacci_mod(10, 0), Err(FibonacciError::ZeroModulus));
+    assert_eq!(
+      pisano_period(u64::MAX),
+      Err(FibonacciError::ModulusTooLarge {
+        m: u64::MAX,
+        max: PISANO_MAX_MODULUS
+      })
+    );
+  }
+}
@@ -0,0 +1,11 @@
+pub mod batch;
+pub mod big_uint;
+pub mod cli;
+pub mod company;
+pub mod fibonacci;
+pub mod lyrics;
+pub mod pig_latin;
+pub mod sequences;
+pub mod statistics;
+pub mod temperature_converter;
+pub mod units;
@@ -0,0 +1,438 @@
+// Lyrics for songs that build up verse by verse, generated from data tables. A song is a
+// handful of templates plus its items, so adding one doesn't take any new code.
+//
+// Templates fill in `{key}` placeholders; `{^key}` does the same with the first letter
+// capitalized. What's available depends on the kind of song, see `CumulativeSong` and
+// `CountdownSong`.
+
+const ONES: [&str; 20] = [
+  "zero",
+  "one",
+  "two",
+  "three",
+  "four",
+  "five",
+  "six",
+  "seven",
+  "eight",
+  "nine",
+  "ten",
+  "eleven",
+  "twelve",
+  "thirteen",
+  "fourteen",
+  "fifteen",
+  "sixteen",
+  "seventeen",
+  "eighteen",
+  "nineteen",
+];
+
+const TENS: [&str; 10] = [
+  "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+// "st", "nd", "rd" or "th". 11, 12 and 13 are "th" even though they end in 1, 2 and 3.
+pub fn ordinal_suffix(n: u64) -> &'static str {
+  match (n % 10, n % 100) {
+    (_, 11..=13) => "th",
+    (1, _) => "st",
+    (2, _) => "nd",
+    (3, _) => "rd",
+    _ => "th",
+  }
+}
+
+// 1 -> "1st", 22 -> "22nd", 113 -> "113th"
+pub fn ordinal(n: u64) -> String {
+  format!("{n}{}", ordinal_suffix(n))
+}
+
+// The number in words, "forty-two". Only goes up to 99, bigger numbers come back as digits.
+pub fn cardinal_word(n: u64) -> String {
+  match n {
+    0..=19 => ONES[n as usize].to_string(),
+    20..=99 if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+    20..=99 => format!("{}-{}", TENS[n as usize / 10], ONES[n as usize % 10]),
+    _ => n.to_string(),
+  }
+}
+
+// The ordinal in words, "twelfth", "forty-second". Past 99 it's "100th" and so on.
+pub fn ordinal_word(n: u64) -> String {
+  if n >= 100 {
+    return ordinal(n);
+  }
+  if n >= 20 && !n.is_multiple_of(10) {
+    return format!("{}-{}", TENS[n as usize / 10], ordinal_word(n % 10));
+  }
+
+  let word = cardinal_word(n);
+  match word.as_str() {
+    "one" => String::from("first"),
+    "two" => String::from("second"),
+    "three" => String::from("third"),
+    "five" => String::from("fifth"),
+    "eight" => String::from("eighth"),
+    "nine" => String::from("ninth"),
+    "twelve" => String::from("twelfth"),
+    // twenty -> twentieth
+    _ if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+    _ => format!("{word}th"),
+  }
+}
+
+fn capitalize(text: &str) -> String {
+  let mut chars = text.chars();
+  match chars.next() {
+    Some(first) => first.to_uppercase().chain(chars).collect(),
+    None => String::new(),
+  }
+}
+
+// Fills `{key}` and `{^key}` placeholders from `values`. Unknown keys are left as they are so
+// a typo in a template shows up in the lyrics instead of quietly disappearing.
+pub fn fill<K: AsRef<str>>(template: &str, values: &[(K, String)]) -> String {
+  let mut filled = String::with_capacity(template.len());
+  let mut rest = template;
+
+  while let Some(start) = rest.find('{') {
+    filled.push_str(&rest[..start]);
+    let end = match rest[start..].find('}') {
+      Some(end) => start + end,
+      None => {
+        rest = &rest[start..];
+        break;
+      }
+    };
+    let key = &rest[start + 1..end];
+    let (key, capitalized) = match key.strip_prefix('^') {
+      Some(key) => (key, true),
+      None => (key, false),
+    };
+
+    match values.iter().find(|(name, _)| name.as_ref() == key) {
+      Some((_, value)) if capitalized => filled.push_str(&capitalize(value)),
+      Some((_, value)) => filled.push_str(value),
+      None => filled.push_str(&rest[start..=end]),
+    }
+    rest = &rest[end + 1..];
+  }
+
+  filled.push_str(rest);
+  filled
+}
+
+pub trait Song {
+  fn title(&self) -> &str;
+
+  fn verse_count(&self) -> usize;
+
+  // Verses are numbered from 1. Asking for one past the end gives an empty string.
+  fn verse(&self, n: usize) -> String;
+
+  fn lyrics(&self) -> String {
+    (1..=self.verse_count())
+      .map(|n| self.verse(n))
+      .collect::<Vec<_>>()
+      .join("\n\n")
+  }
+}
+
+// A song where verse n sings item n and then every item before it, newest first, like
+// "The Twelve Days of Christmas". Each item is a row of fields.
+//
+// Every template can use `{n}` (the verse number), `{ordinal}` ("third") and `{number}`
+// ("three"). `intro` and `outro` also see the fields of the verse's newest item as `{0}`,
+// `{1}` and so on, the line templates see the fields of the item they're singing.
+#[derive(Debug, Clone, Copy)]
+pub struct CumulativeSong {
+  pub title: &'static str,
+  pub items: &'static [&'static [&'static str]],
+  pub intro: &'static str,
+  // sung for every item in the verse, newest first
+  pub line: &'static str,
+  // sung instead of `line` for the first item when it's the only one in the verse
+  pub first_alone: Option<&'static str>,
+  // sung instead of `line` for the first item at the end of a longer verse
+  pub first_last: Option<&'static str>,
+  pub outro: &'static str,
+}
+
+impl CumulativeSong {
+  fn values(&self, verse: usize, item: usize) -> Vec<(String, String)> {
+    let mut values = vec![
+      (String::from("n"), verse.to_string()),
+      (String::from("ordinal"), ordinal_word(verse as u64)),
+      (String::from("number"), cardinal_word(verse as u64)),
+    ];
+    for (index, field) in self.items[item].iter().enumerate() {
+      values.push((index.to_string(), field.to_string()));
+    }
+    values
+  }
+}
+
+impl Song for CumulativeSong {
+  fn title(&self) -> &str {
+    self.title
+  }
+
+  fn verse_count(&self) -> usize {
+    self.items.len()
+  }
+
+  fn verse(&self, n: usize) -> String {
+    if n == 0 || n > self.items.len() {
+      return String::new();
+    }
+    let newest = self.values(n, n - 1);
+    let mut lines = vec![fill(self.intro, &newest)];
+
+    for item in (0..n).rev() {
+      let template = match (item, n) {
+        (0, 1) => self.first_alone.unwrap_or(self.line),
+        (0, _) => self.first_last.unwrap_or(self.line),
+        _ => self.line,
+      };
+      lines.push(fill(template, &self.values(n, item)));
+    }
+    lines.push(fill(self.outro, &newest));
+
+    lines
+      .into_iter()
+      .filter(|line| !line.is_empty())
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+// A song counting down from `start` to none, like "99 Bottles of Beer".
+//
+// Templates can use `{count}` ("99 bottles", "1 bottle", "no more bottles") and `{next}`, the
+// same for one less. After the last verse `{next}` wraps around to `start` again.
+#[derive(Debug, Clone, Copy)]
+pub struct CountdownSong {
+  pub title: &'static str,
+  pub start: u64,
+  pub singular: &'static str,
+  pub plural: &'static str,
+  // how zero is said, as in "no more bottles"
+  pub none: &'static str,
+  pub verse: &'static str,
+  // used instead of `verse` when there's exactly one left
+  pub one_left: Option<&'static str>,
+  // the verse once there are none left
+  pub none_left: &'static str,
+}
+
+impl CountdownSong {
+  fn count(&self, count: u64) -> String {
+    match count {
+      0 => format!("{} {}", self.none, self.plural),
+      1 => format!("1 {}", self.singular),
+      count => format!("{count} {}", self.plural),
+    }
+  }
+}
+
+impl Song for CountdownSong {
+  fn title(&self) -> &str {
+    self.title
+  }
+
+  fn verse_count(&self) -> usize {
+    self.start as usize + 1
+  }
+
+  fn verse(&self, n: usize) -> String {
+    if n == 0 || n > self.verse_count() {
+      return String::new();
+    }
+    let count = self.start - (n as u64 - 1);
+    let (template, next) = match count {
+      0 => (self.none_left, self.start),
+      1 => (self.one_left.unwrap_or(self.verse), 0),
+      count => (self.verse, count - 1),
+    };
+    fill(
+      template,
+      &[("count", self.count(count)), ("next", self.count(next))],
+    )
+  }
+}
+
+pub const TWELVE_DAYS_OF_CHRISTMAS: CumulativeSong = CumulativeSong {
+  title: "The Twelve Days of Christmas",
+  items: &[
+    &["a partridge in a pear tree"],
+    &["two turtle doves"],
+    &["three French hens"],
+    &["four calling birds"],
+    &["five gold rings"],
+    &["six geese a-laying"],
+    &["seven swans a-swimming"],
+    &["eight maids a-milking"],
+    &["nine ladies dancing"],
+    &["ten lords a-leaping"],
+    &["eleven pipers piping"],
+    &["twelve drummers drumming"],
+  ],
+  intro: "On the {ordinal} day of Christmas my true love sent to me",
+  line: "{^0},",
+  first_alone: Some("{^0}."),
+  first_last: Some("And {0}."),
+  outro: "",
+};
+
+pub const OLD_MACDONALD: CumulativeSong = CumulativeSong {
+  title: "Old MacDonald Had a Farm",
+  items: &[
+    &["cow", "moo"],
+    &["duck", "quack"],
+    &["dog", "woof"],
+    &["horse", "neigh"],
+    &["sheep", "baa"],
+  ],
+  intro: "Old MacDonald had a farm, E-I-E-I-O,\nAnd on his farm he had a {0}, E-I-E-I-O,",
+  line: "With a {1} {1} here and a {1} {1} there,\nHere a {1}, there a {1}, everywhere a {1} {1},",
+  first_alone: None,
+  first_last: None,
+  outro: "Old MacDonald had a farm, E-I-E-I-O.",
+};
+
+pub const NINETY_NINE_BOTTLES: CountdownSong = CountdownSong {
+  title: "99 Bottles of Beer",
+  start: 99,
+  singular: "bottle",
+  plural: "bottles",
+  none: "no more",
+  verse: "{^count} of beer on the wall, {count} of beer.\n\
+          Take one down and pass it around, {next} of beer on the wall.",
+  one_left: Some(
+    "{^count} of beer on the wall, {count} of beer.\n\
+     Take it down and pass it around, {next} of beer on the wall.",
+  ),
+  none_left: "{^count} of beer on the wall, {count} of beer.\n\
+              Go to the store and buy some more, {next} of beer on the wall.",
+};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn formats_ordinals() {
+    let suffixed: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 101, 111, 112]
+      .into_iter()
+      .map(ordinal)
+      .collect();
+    assert_eq!(
+      suffixed,
+      [
+        "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "101st", "111th",
+        "112th"
+      ]
+    );
+
+    let words: Vec<String> = [1, 2, 3, 5, 8, 9, 12, 14, 20, 21, 42, 99, 100]
+      .into_iter()
+      .map(ordinal_word)
+      .collect();
+    assert_eq!(
+      words,
+      [
+        "first",
+        "second",
+        "third",
+        "fifth",
+        "eighth",
+        "ninth",
+        "twelfth",
+        "fourteenth",
+        "twentieth",
+        "twenty-first",
+        "forty-second",
+        "ninety-ninth",
+        "100th"
+      ]
+    );
+    assert_eq!(cardinal_word(0), "zero");
+    assert_eq!(cardinal_word(77), "seventy-seven");
+  }
+
+  #[test]
+  fn fills_templates() {
+    let values = [("animal", String::from("cow"))];
+    assert_eq!(fill("a {animal}, {^animal}!", &values), "a cow, Cow!");
+    assert_eq!(fill("{missing} {animal", &values), "{missing} {animal");
+  }
+
+  #[test]
+  fn twelve_days_of_christmas() {
+    let song = TWELVE_DAYS_OF_CHRISTMAS;
+    assert_eq!(song.verse_count(), 12);
+    assert_eq!(
+      song.verse(1),
+      "On the first day of Christmas my true love sent to me\nA partridge in a pear tree."
+    );
+    assert_eq!(
+      song.verse(3),
+      "On the third day of Christmas my true love sent to me\n\
+       Three French hens,\n\
+       Two turtle doves,\n\
+       And a partridge in a pear tree."
+    );
+    assert!(song.verse(12).starts_with("On the twelfth day"));
+    assert_eq!(song.verse(12).lines().count(), 13);
+    assert_eq!(song.verse(13), "");
+    assert_eq!(song.lyrics().split("\n\n").count(), 12);
+  }
+
+  #[test]
+  fn old_macdonald_repeats_every_animal_so_far() {
+    let verse = OLD_MACDONALD.verse(2);
+    assert_eq!(
+      verse,
+      "Old MacDonald had a farm, E-I-E-I-O,\n\
+       And on his farm he had a duck, E-I-E-I-O,\n\
+       With a quack quack here and a quack quack there,\n\
+       Here a quack, there a quack, everywhere a quack quack,\n\
+       With a moo moo here and a moo moo there,\n\
+       Here a moo, there a moo, everywhere a moo moo,\n\
+       Old MacDonald had a farm, E-I-E-I-O."
+    );
+  }
+
+  #[test]
+  fn bottles_count_down_and_start_over() {
+    let song = NINETY_NINE_BOTTLES;
+    assert_eq!(song.verse_count(), 100);
+    assert_eq!(
+      song.verse(1),
+      "99 bottles of beer on the wall, 99 bottles of beer.\n\
+       Take one down and pass it around, 98 bottles of beer on the wall."
+    );
+    assert_eq!(
+      song.verse(98),
+      "2 bottles of beer on the wall, 2 bottles of beer.\n\
+       Take one down and pass it around, 1 bottle of beer on the wall."
+    );
+    assert_eq!(
+      song.verse(99),
+      "1 bottle of beer on the wall, 1 bottle of beer.\n\
+       Take it down and pass it around, no more bottles of beer on the wall."
+    );
+    assert_eq!(
+      song.verse(100),
+      "No more bottles of beer on the wall, no more bottles of beer.\n\
+       Go to the store and buy some more, 99 bottles of beer on the wall."
+    );
+
+    let short = CountdownSong {
+      start: 3,
+      ..NINETY_NINE_BOTTLES
+    };
+    assert_eq!(short.lyrics().split("\n\n").count(), 4);
+  }
+}
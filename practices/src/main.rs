@@ -1,12 +1,72 @@
-mod fibonacci;
-mod temperature_converter;
+use practices::{
+    batch::{self, Options},
+    cli::{self, CliError, Command, USAGE},
+};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    process,
+};
 
 fn main() {
-    let one_fah_in_cels = temperature_converter::convert_to_celsius(1.0);
-    let one_cels_in_fah = temperature_converter::convert_to_fahrenheit(1.0);
-    let twentieth_fibonacci = fibonacci::generate_nth_fibonacci_number(20);
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            match &error {
+                CliError::Usage(message) => eprintln!("error: {message}\n\n{USAGE}"),
+                CliError::Invalid(message) => eprintln!("error: {message}"),
+            }
+            process::exit(error.exit_code());
+        }
+    };
 
-    println!("1 fahrenheit = {one_fah_in_cels} celsius");
-    println!("1 celsius = {one_cels_in_fah} fahrenheit");
-    println!("20th fibonacci is: {twentieth_fibonacci}");
+    match command {
+        Command::Repl => {
+            cli::repl(io::stdin().lock(), io::stdout().lock()).expect("Failed to run the repl");
+        }
+        Command::Batch {
+            input,
+            output,
+            options,
+        } => run_batch(&input, output, &options),
+        command => match cli::evaluate(&command) {
+            Ok(text) => println!("{text}"),
+            Err(error) => {
+                eprintln!("error: {}", error.message());
+                process::exit(error.exit_code());
+            }
+        },
+    }
+}
+
+fn run_batch(input: &Path, output: Option<PathBuf>, options: &Options) {
+    let fail = |message: String| -> ! {
+        eprintln!("error: {message}");
+        process::exit(1);
+    };
+    let reader = File::open(input)
+        .map(BufReader::new)
+        .unwrap_or_else(|error| fail(format!("couldn't read {}: {error}", input.display())));
+    let writer: Box<dyn Write> = match &output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).unwrap_or_else(|error| {
+            fail(format!("couldn't create {}: {error}", path.display()))
+        }))),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    let report = batch::convert_stream(reader, writer, options)
+        .unwrap_or_else(|error| fail(format!("{}: {error}", input.display())));
+    for error in &report.errors {
+        eprintln!("{}: {error}", input.display());
+    }
+    eprintln!(
+        "Converted {} rows, skipped {} malformed ones.",
+        report.converted,
+        report.errors.len()
+    );
+    if !report.errors.is_empty() {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,100 @@
+// Pig Latin, the Book's way: a word starting with a consonant moves that consonant to the end
+// and adds "ay" ("first" becomes "irst-fay"), a word starting with a vowel just gets "hay"
+// ("apple" becomes "apple-hay").
+//
+// Everything works on chars rather than bytes, so "élan" and "привет" don't get cut in the
+// middle of a letter. Letters with accents count as the vowel they're built on.
+
+const VOWELS: &str = "aeiouáàâäãåéèêëíìîïóòôöõúùûüýÿæœ";
+
+fn is_vowel(c: char) -> bool {
+  c.to_lowercase().all(|lower| VOWELS.contains(lower))
+}
+
+// Translates a single word. Anything that doesn't start with a letter is left as it is.
+pub fn translate_word(word: &str) -> String {
+  let mut chars = word.chars();
+  let first = match chars.next() {
+    Some(first) if first.is_alphabetic() => first,
+    _ => return word.to_string(),
+  };
+
+  if is_vowel(first) {
+    return format!("{word}-hay");
+  }
+
+  let rest = chars.as_str();
+  // "Rust" -> "Ust-ray" rather than "ust-Ray", the capital stays at the front
+  let starts_upper = first.is_uppercase();
+  let mut rest_chars = rest.chars();
+  let rest = match rest_chars.next() {
+    Some(second) if starts_upper => second.to_uppercase().chain(rest_chars).collect(),
+    _ => rest.to_string(),
+  };
+  let moved: String = if starts_upper {
+    first.to_lowercase().collect()
+  } else {
+    first.to_string()
+  };
+  format!("{rest}-{moved}ay")
+}
+
+// Translates every word of a sentence, keeping the spaces and punctuation around them.
+// An apostrophe between two letters belongs to the word, so "don't" stays one word.
+pub fn translate(text: &str) -> String {
+  let mut translated = String::with_capacity(text.len() * 2);
+  let mut word = String::new();
+  let mut chars = text.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    let inside_word = c.is_alphabetic()
+      || (c == '\'' && !word.is_empty() && chars.peek().is_some_and(|next| next.is_alphabetic()));
+    if inside_word {
+      word.push(c);
+      continue;
+    }
+    if !word.is_empty() {
+      translated.push_str(&translate_word(&word));
+      word.clear();
+    }
+    translated.push(c);
+  }
+  if !word.is_empty() {
+    translated.push_str(&translate_word(&word));
+  }
+
+  translated
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn translates_words_like_the_book() {
+    assert_eq!(translate_word("first"), "irst-fay");
+    assert_eq!(translate_word("apple"), "apple-hay");
+    assert_eq!(translate_word("Rust"), "Ust-ray");
+    assert_eq!(translate_word("I"), "I-hay");
+    assert_eq!(translate_word("42"), "42");
+    assert_eq!(translate_word(""), "");
+  }
+
+  #[test]
+  fn handles_multibyte_letters() {
+    assert_eq!(translate_word("élan"), "élan-hay");
+    assert_eq!(translate_word("Über"), "Über-hay");
+    assert_eq!(translate_word("ñame"), "ame-ñay");
+    assert_eq!(translate_word("привет"), "ривет-пay");
+    assert_eq!(translate_word("Straße"), "Traße-say");
+  }
+
+  #[test]
+  fn translates_sentences_keeping_punctuation() {
+    assert_eq!(
+      translate("Hello, world! Don't panic."),
+      "Ello-hay, orld-way! On't-day anic-pay."
+    );
+    assert_eq!(translate("  an  apple\n"), "  an-hay  apple-hay\n");
+  }
+}
@@ -0,0 +1,69 @@
+// Fibonacci-like sequences: every term is the sum of the `k` terms before it, where `k` is
+// how many seeds the sequence starts from. Fibonacci is seeds [0, 1], Lucas is [2, 1] and
+// tribonacci is [0, 0, 1]. Like `fibonacci::Fibonacci`, iterating stops instead of overflowing.
+#[derive(Debug, Clone)]
+pub struct Sequence {
+  // the last `k` terms, oldest first; `None` once a term no longer fits in a u64
+  window: Vec<Option<u64>>,
+}
+
+impl Sequence {
+  pub fn with_seeds(seeds: &[u64]) -> Sequence {
+    Sequence {
+      window: seeds.iter().map(|&seed| Some(seed)).collect(),
+    }
+  }
+
+  pub fn fibonacci() -> Sequence {
+    Sequence::with_seeds(&[0, 1])
+  }
+
+  pub fn lucas() -> Sequence {
+    Sequence::with_seeds(&[2, 1])
+  }
+
+  pub fn tribonacci() -> Sequence {
+    Sequence::with_seeds(&[0, 0, 1])
+  }
+}
+
+impl Iterator for Sequence {
+  type Item = u64;
+
+  fn next(&mut self) -> Option<u64> {
+    let current = (*self.window.first()?)?;
+    let next = self
+      .window
+      .iter()
+      .try_fold(0u64, |sum, term| sum.checked_add((*term)?));
+    self.window.remove(0);
+    self.window.push(next);
+    Some(current)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fibonacci::Fibonacci;
+
+  #[test]
+  fn well_known_sequences() {
+    let lucas: Vec<u64> = Sequence::lucas().take(8).collect();
+    assert_eq!(lucas, [2, 1, 3, 4, 7, 11, 18, 29]);
+
+    let tribonacci: Vec<u64> = Sequence::tribonacci().take(9).collect();
+    assert_eq!(tribonacci, [0, 0, 1, 1, 2, 4, 7, 13, 24]);
+
+    assert!(Sequence::fibonacci().eq(Fibonacci::new()));
+  }
+
+  #[test]
+  fn custom_seeds_and_overflow() {
+    let custom: Vec<u64> = Sequence::with_seeds(&[1, 1, 1, 1]).take(7).collect();
+    assert_eq!(custom, [1, 1, 1, 1, 4, 7, 13]);
+
+    assert_eq!(Sequence::with_seeds(&[]).next(), None);
+    assert_eq!(Sequence::with_seeds(&[u64::MAX, 1]).count(), 2);
+  }
+}
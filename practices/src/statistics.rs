@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+// The mean, as a float since it usually isn't a whole number. Summed as an i64 so big lists
+// of big numbers don't overflow.
+pub fn mean(numbers: &[i32]) -> Option<f64> {
+  if numbers.is_empty() {
+    return None;
+  }
+  let sum: i64 = numbers.iter().map(|&number| i64::from(number)).sum();
+  Some(sum as f64 / numbers.len() as f64)
+}
+
+// The middle value once sorted. With an even count there are two middles and the median is
+// halfway between them, which is why this is a float too.
+pub fn median(numbers: &[i32]) -> Option<f64> {
+  if numbers.is_empty() {
+    return None;
+  }
+  let mut sorted = numbers.to_vec();
+  sorted.sort_unstable();
+  let middle = sorted.len() / 2;
+
+  if sorted.len() % 2 == 1 {
+    Some(f64::from(sorted[middle]))
+  } else {
+    Some((f64::from(sorted[middle - 1]) + f64::from(sorted[middle])) / 2.0)
+  }
+}
+
+// Every value that shows up the most often, smallest first. [1, 2, 2, 3, 3] has two.
+pub fn modes(numbers: &[i32]) -> Vec<i32> {
+  let mut counts = HashMap::new();
+  for &number in numbers {
+    *counts.entry(number).or_insert(0) += 1;
+  }
+
+  let highest = counts.values().copied().max().unwrap_or(0);
+  let mut modes: Vec<i32> = counts
+    .into_iter()
+    .filter(|&(_, count)| count == highest)
+    .map(|(number, _)| number)
+    .collect();
+  modes.sort_unstable();
+  modes
+}
+
+// The value that shows up the most often. When there's a tie the smallest one wins, so the
+// answer doesn't depend on the order of the list.
+pub fn mode(numbers: &[i32]) -> Option<i32> {
+  modes(numbers).first().copied()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn median_of_odd_and_even_lists() {
+    assert_eq!(median(&[5, 1, 3]), Some(3.0));
+    assert_eq!(median(&[4, 1, 3, 2]), Some(2.5));
+    assert_eq!(median(&[i32::MAX, i32::MAX]), Some(f64::from(i32::MAX)));
+    assert_eq!(median(&[]), None);
+  }
+
+  #[test]
+  fn mode_picks_the_most_common_value() {
+    assert_eq!(mode(&[1, 3, 3, 7, 7, 7]), Some(7));
+    assert_eq!(modes(&[3, 2, 2, 3, 1]), [2, 3]);
+    assert_eq!(mode(&[3, 2, 2, 3, 1]), Some(2));
+    assert_eq!(mode(&[]), None);
+    assert_eq!(modes(&[]), Vec::<i32>::new());
+  }
+
+  #[test]
+  fn mean_does_not_overflow() {
+    assert_eq!(mean(&[1, 2, 3, 4]), Some(2.5));
+    assert_eq!(mean(&[i32::MAX, i32::MAX]), Some(f64::from(i32::MAX)));
+    assert_eq!(mean(&[]), None);
+  }
+}
@@ -1,3 +1,6 @@
+use std::fmt;
+use std::str::FromStr;
+
 pub fn convert_to_fahrenheit(celsius: f64) -> f64 {
   ((celsius * 9.0) / 5.0) + 32.0
 }
@@ -5,3 +8,302 @@ pub fn convert_to_fahrenheit(celsius: f64) -> f64 {
 pub fn convert_to_celsius(fahrenheit: f64) -> f64 {
   ((fahrenheit - 32.0) * 5.0) / 9.0
 }
+
+// Rounding in the conversions can land a hair below absolute zero, that still counts as zero
+const ABSOLUTE_ZERO_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scale {
+  Celsius,
+  Fahrenheit,
+  Kelvin,
+  Rankine,
+  Reaumur,
+}
+
+impl Scale {
+  pub const ALL: [Scale; 5] = [
+    Scale::Celsius,
+    Scale::Fahrenheit,
+    Scale::Kelvin,
+    Scale::Rankine,
+    Scale::Reaumur,
+  ];
+
+  pub fn symbol(self) -> &'static str {
+    match self {
+      Scale::Celsius => "°C",
+      Scale::Fahrenheit => "°F",
+      Scale::Kelvin => "K",
+      Scale::Rankine => "°R",
+      Scale::Reaumur => "°Ré",
+    }
+  }
+
+  // Every conversion goes through kelvin, so a new scale only needs these two functions
+  pub fn to_kelvin(self, value: f64) -> f64 {
+    match self {
+      Scale::Celsius => value + 273.15,
+      Scale::Fahrenheit => (value + 459.67) * 5.0 / 9.0,
+      Scale::Kelvin => value,
+      Scale::Rankine => value * 5.0 / 9.0,
+      Scale::Reaumur => value * 5.0 / 4.0 + 273.15,
+    }
+  }
+
+  pub fn from_kelvin(self, kelvin: f64) -> f64 {
+    match self {
+      Scale::Celsius => kelvin - 273.15,
+      Scale::Fahrenheit => kelvin * 9.0 / 5.0 - 459.67,
+      Scale::Kelvin => kelvin,
+      Scale::Rankine => kelvin * 9.0 / 5.0,
+      Scale::Reaumur => (kelvin - 273.15) * 4.0 / 5.0,
+    }
+  }
+
+  pub fn absolute_zero(self) -> f64 {
+    self.from_kelvin(0.0)
+  }
+}
+
+impl fmt::Display for Scale {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Scale::Celsius => "Celsius",
+      Scale::Fahrenheit => "Fahrenheit",
+      Scale::Kelvin => "Kelvin",
+      Scale::Rankine => "Rankine",
+      Scale::Reaumur => "Réaumur",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for Scale {
+  type Err = TemperatureError;
+
+  // Accepts symbols with or without the degree sign ("°F", "F") and full names ("kelvin").
+  // "R" is Rankine, Réaumur is "Ré" or "Re".
+  fn from_str(input: &str) -> Result<Scale, TemperatureError> {
+    let name = input.trim().trim_start_matches('°').to_lowercase();
+    match name.as_str() {
+      "c" | "celsius" => Ok(Scale::Celsius),
+      "f" | "fahrenheit" => Ok(Scale::Fahrenheit),
+      "k" | "kelvin" => Ok(Scale::Kelvin),
+      "r" | "ra" | "rankine" => Ok(Scale::Rankine),
+      "ré" | "re" | "réaumur" | "reaumur" => Ok(Scale::Reaumur),
+      _ => Err(TemperatureError::UnknownScale(input.trim().to_string())),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperatureError {
+  BelowAbsoluteZero { value: f64, scale: Scale },
+  NotFinite,
+  UnknownScale(String),
+  InvalidNumber(String),
+}
+
+impl fmt::Display for TemperatureError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      TemperatureError::BelowAbsoluteZero { value, scale } => write!(
+        f,
+        "{value} {} is below absolute zero ({} {})",
+        scale.symbol(),
+        scale.absolute_zero(),
+        scale.symbol()
+      ),
+      TemperatureError::NotFinite => write!(f, "a temperature has to be a finite number"),
+      TemperatureError::UnknownScale(scale) => write!(
+        f,
+        "unknown temperature scale `{scale}`, expected one of C, F, K, R or Ré"
+      ),
+      TemperatureError::InvalidNumber(number) => write!(f, "`{number}` is not a number"),
+    }
+  }
+}
+
+impl std::error::Error for TemperatureError {}
+
+// A temperature that is always at or above absolute zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Temperature {
+  value: f64,
+  scale: Scale,
+}
+
+impl Temperature {
+  pub fn new(value: f64, scale: Scale) -> Result<Temperature, TemperatureError> {
+    if !value.is_finite() {
+      return Err(TemperatureError::NotFinite);
+    }
+    if scale.to_kelvin(value) < -ABSOLUTE_ZERO_TOLERANCE {
+      return Err(TemperatureError::BelowAbsoluteZero { value, scale });
+    }
+    Ok(Temperature { value, scale })
+  }
+
+  pub fn value(&self) -> f64 {
+    self.value
+  }
+
+  pub fn scale(&self) -> Scale {
+    self.scale
+  }
+
+  pub fn kelvin(&self) -> f64 {
+    self.scale.to_kelvin(self.value).max(0.0)
+  }
+
+  pub fn to(&self, scale: Scale) -> Temperature {
+    Temperature {
+      value: scale.from_kelvin(self.kelvin()),
+      scale,
+    }
+  }
+}
+
+pub fn convert(value: f64, from: Scale, to: Scale) -> Result<f64, TemperatureError> {
+  Ok(Temperature::new(value, from)?.to(to).value())
+}
+
+// Conversions pick up float noise (100 °C comes out as 211.99999999999994 °F), so converted
+// values are shown with at most six decimals and no trailing zeros.
+pub fn display_value(value: f64) -> String {
+  let number = format!("{value:.6}");
+  let number = number.trim_end_matches('0').trim_end_matches('.');
+  match number {
+    "-0" => String::from("0"),
+    number => number.to_string(),
+  }
+}
+
+impl fmt::Display for Temperature {
+  // "98.6 °F", "300 K"; a precision like `{:.1}` is applied to the number
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match f.precision() {
+      Some(precision) => write!(f, "{:.*} {}", precision, self.value, self.scale.symbol()),
+      None => write!(f, "{} {}", self.value, self.scale.symbol()),
+    }
+  }
+}
+
+// Splits "98.6 °F" into "98.6" and " °F". The number ends where the first character that
+// can't be part of one shows up.
+pub(crate) fn split_number(input: &str) -> (&str, &str) {
+  let split = input
+    .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')))
+    .unwrap_or(input.len());
+  // an exponent's `e` has to be followed by a digit (maybe after a sign), otherwise it's the
+  // start of the unit
+  let split = match input[..split].rfind(['e', 'E']) {
+    Some(e) => {
+      let exponent = &input[e + 1..split];
+      let digits = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+      if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        split
+      } else {
+        e
+      }
+    }
+    None => split,
+  };
+  input.split_at(split)
+}
+
+impl FromStr for Temperature {
+  type Err = TemperatureError;
+
+  // "98.6F", "98.6 °F", "300 K", "-40 celsius"
+  fn from_str(input: &str) -> Result<Temperature, TemperatureError> {
+    let input = input.trim();
+    let (number, scale) = split_number(input);
+    let value: f64 = number
+      .trim()
+      .parse()
+      .map_err(|_| TemperatureError::InvalidNumber(input.to_string()))?;
+    Temperature::new(value, scale.parse()?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+  }
+
+  #[test]
+  fn converts_between_every_scale() {
+    let boiling = Temperature::new(100.0, Scale::Celsius).unwrap();
+    assert!(close(boiling.to(Scale::Fahrenheit).value(), 212.0));
+    assert!(close(boiling.to(Scale::Kelvin).value(), 373.15));
+    assert!(close(boiling.to(Scale::Rankine).value(), 671.67));
+    assert!(close(boiling.to(Scale::Reaumur).value(), 80.0));
+
+    for from in Scale::ALL {
+      for to in Scale::ALL {
+        let there = convert(42.0, from, to).unwrap();
+        assert!(close(convert(there, to, from).unwrap(), 42.0), "{from} -> {to}");
+      }
+    }
+    let one_fahrenheit = convert(1.0, Scale::Fahrenheit, Scale::Celsius).unwrap();
+    assert!(close(one_fahrenheit, convert_to_celsius(1.0)));
+  }
+
+  #[test]
+  fn rejects_temperatures_below_absolute_zero() {
+    assert_eq!(
+      Temperature::new(-1.0, Scale::Kelvin),
+      Err(TemperatureError::BelowAbsoluteZero {
+        value: -1.0,
+        scale: Scale::Kelvin
+      })
+    );
+    assert!(Temperature::new(-273.16, Scale::Celsius).is_err());
+    assert!(Temperature::new(-459.67, Scale::Fahrenheit).is_ok());
+    assert_eq!(
+      Temperature::new(f64::NAN, Scale::Celsius),
+      Err(TemperatureError::NotFinite)
+    );
+  }
+
+  #[test]
+  fn parses_and_displays_temperatures() {
+    let fever: Temperature = "98.6F".parse().unwrap();
+    assert_eq!(fever, Temperature::new(98.6, Scale::Fahrenheit).unwrap());
+    assert_eq!(fever.to_string(), "98.6 °F");
+    assert_eq!("300 K".parse::<Temperature>().unwrap().scale(), Scale::Kelvin);
+    assert_eq!("-40 celsius".parse::<Temperature>().unwrap().value(), -40.0);
+    assert_eq!("1e2 °Ré".parse::<Temperature>().unwrap().value(), 100.0);
+    assert_eq!(split_number("1e+5 K"), ("1e+5", " K"));
+    assert_eq!("1e+2 K".parse::<Temperature>().unwrap().value(), 100.0);
+    assert_eq!("2.5e-1C".parse::<Temperature>().unwrap().value(), 0.25);
+    assert_eq!("5 Re".parse::<Temperature>().unwrap().scale(), Scale::Reaumur);
+    assert_eq!(format!("{:.1}", fever.to(Scale::Celsius)), "37.0 °C");
+    assert_eq!(display_value(211.99999999999994), "212");
+    assert_eq!(display_value(-0.0000001), "0");
+    assert_eq!(display_value(26.85), "26.85");
+
+    for scale in Scale::ALL {
+      let temperature = Temperature::new(12.25, scale).unwrap();
+      assert_eq!(temperature.to_string().parse::<Temperature>(), Ok(temperature));
+    }
+  }
+
+  #[test]
+  fn reports_bad_input() {
+    assert_eq!(
+      "hot".parse::<Temperature>(),
+      Err(TemperatureError::InvalidNumber(String::from("hot")))
+    );
+    assert_eq!(
+      "12 X".parse::<Temperature>(),
+      Err(TemperatureError::UnknownScale(String::from("X")))
+    );
+    assert!("-500 C".parse::<Temperature>().is_err());
+  }
+}
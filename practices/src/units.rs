@@ -0,0 +1,673 @@
+use crate::temperature_converter::split_number;
+use std::{
+  fmt,
+  ops::{Add, Div, Mul, Sub},
+  str::FromStr,
+};
+
+// Units of measure that convert by a fixed factor, in the same style as
+// `temperature_converter`: every conversion goes through one base unit per dimension, so a
+// new unit only needs its size in that base unit. The dimension is part of the type, a
+// `Quantity<LengthUnit>` and a `Quantity<DurationUnit>` can't be mixed up:
+//
+//   let walk = Length::new(5.0, LengthUnit::Kilometer)? + Length::new(300.0, LengthUnit::Foot)?;
+//   let pace = walk / Duration::new(1.0, DurationUnit::Hour)?;   // a Speed
+//   walk + Duration::new(1.0, DurationUnit::Hour)?;             // doesn't compile
+pub trait Unit: Copy + PartialEq + fmt::Debug + 'static {
+  // the dimension's name, as in "unknown length unit"
+  const DIMENSION: &'static str;
+  const ALL: &'static [Self];
+
+  fn symbol(self) -> &'static str;
+
+  // How many base units one of this unit is.
+  fn factor(self) -> f64;
+
+  // Other spellings accepted when parsing, besides the symbol. Compared case-insensitively.
+  fn names(self) -> &'static [&'static str];
+
+  fn to_base(self, value: f64) -> f64 {
+    value * self.factor()
+  }
+
+  fn value_from_base(self, base: f64) -> f64 {
+    base / self.factor()
+  }
+
+  fn parse(input: &str) -> Result<Self, UnitError> {
+    let name = input.trim().to_lowercase();
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|unit| unit.symbol().to_lowercase() == name || unit.names().contains(&name.as_str()))
+      .ok_or_else(|| UnitError::UnknownUnit {
+        unit: input.trim().to_string(),
+        dimension: Self::DIMENSION,
+      })
+  }
+}
+
+// Base unit: the meter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+  Millimeter,
+  Centimeter,
+  Meter,
+  Kilometer,
+  Inch,
+  Foot,
+  Yard,
+  Mile,
+}
+
+impl Unit for LengthUnit {
+  const DIMENSION: &'static str = "length";
+  const ALL: &'static [LengthUnit] = &[
+    LengthUnit::Millimeter,
+    LengthUnit::Centimeter,
+    LengthUnit::Meter,
+    LengthUnit::Kilometer,
+    LengthUnit::Inch,
+    LengthUnit::Foot,
+    LengthUnit::Yard,
+    LengthUnit::Mile,
+  ];
+
+  fn symbol(self) -> &'static str {
+    match self {
+      LengthUnit::Millimeter => "mm",
+      LengthUnit::Centimeter => "cm",
+      LengthUnit::Meter => "m",
+      LengthUnit::Kilometer => "km",
+      LengthUnit::Inch => "in",
+      LengthUnit::Foot => "ft",
+      LengthUnit::Yard => "yd",
+      LengthUnit::Mile => "mi",
+    }
+  }
+
+  fn factor(self) -> f64 {
+    match self {
+      LengthUnit::Millimeter => 0.001,
+      LengthUnit::Centimeter => 0.01,
+      LengthUnit::Meter => 1.0,
+      LengthUnit::Kilometer => 1_000.0,
+      LengthUnit::Inch => 0.0254,
+      LengthUnit::Foot => 0.3048,
+      LengthUnit::Yard => 0.9144,
+      LengthUnit::Mile => 1_609.344,
+    }
+  }
+
+  fn names(self) -> &'static [&'static str] {
+    match self {
+      LengthUnit::Millimeter => &["millimeter", "millimeters", "millimetre", "millimetres"],
+      LengthUnit::Centimeter => &["centimeter", "centimeters", "centimetre", "centimetres"],
+      LengthUnit::Meter => &["meter", "meters", "metre", "metres"],
+      LengthUnit::Kilometer => &["kilometer", "kilometers", "kilometre", "kilometres"],
+      LengthUnit::Inch => &["inch", "inches", "\""],
+      LengthUnit::Foot => &["foot", "feet", "'"],
+      LengthUnit::Yard => &["yard", "yards"],
+      LengthUnit::Mile => &["mile", "miles"],
+    }
+  }
+}
+
+// Base unit: the kilogram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MassUnit {
+  Milligram,
+  Gram,
+  Kilogram,
+  Tonne,
+  Ounce,
+  Pound,
+  Stone,
+}
+
+impl Unit for MassUnit {
+  const DIMENSION: &'static str = "mass";
+  const ALL: &'static [MassUnit] = &[
+    MassUnit::Milligram,
+    MassUnit::Gram,
+    MassUnit::Kilogram,
+    MassUnit::Tonne,
+    MassUnit::Ounce,
+    MassUnit::Pound,
+    MassUnit::Stone,
+  ];
+
+  fn symbol(self) -> &'static str {
+    match self {
+      MassUnit::Milligram => "mg",
+      MassUnit::Gram => "g",
+      MassUnit::Kilogram => "kg",
+      MassUnit::Tonne => "t",
+      MassUnit::Ounce => "oz",
+      MassUnit::Pound => "lb",
+      MassUnit::Stone => "st",
+    }
+  }
+
+  fn factor(self) -> f64 {
+    match self {
+      MassUnit::Milligram => 0.000_001,
+      MassUnit::Gram => 0.001,
+      MassUnit::Kilogram => 1.0,
+      MassUnit::Tonne => 1_000.0,
+      MassUnit::Ounce => 0.028_349_523_125,
+      MassUnit::Pound => 0.453_592_37,
+      MassUnit::Stone => 6.350_293_18,
+    }
+  }
+
+  fn names(self) -> &'static [&'static str] {
+    match self {
+      MassUnit::Milligram => &["milligram", "milligrams"],
+      MassUnit::Gram => &["gram", "grams"],
+      MassUnit::Kilogram => &["kilogram", "kilograms", "kilo", "kilos"],
+      MassUnit::Tonne => &["tonne", "tonnes"],
+      MassUnit::Ounce => &["ounce", "ounces"],
+      MassUnit::Pound => &["pound", "pounds", "lbs"],
+      MassUnit::Stone => &["stone", "stones"],
+    }
+  }
+}
+
+// Base unit: the liter. Cups, pints, quarts and gallons are the US ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VolumeUnit {
+  Milliliter,
+  Liter,
+  CubicMeter,
+  Teaspoon,
+  Tablespoon,
+  Cup,
+  Pint,
+  Quart,
+  Gallon,
+}
+
+impl Unit for VolumeUnit {
+  const DIMENSION: &'static str = "volume";
+  const ALL: &'static [VolumeUnit] = &[
+    VolumeUnit::Milliliter,
+    VolumeUnit::Liter,
+    VolumeUnit::CubicMeter,
+    VolumeUnit::Teaspoon,
+    VolumeUnit::Tablespoon,
+    VolumeUnit::Cup,
+    VolumeUnit::Pint,
+    VolumeUnit::Quart,
+    VolumeUnit::Gallon,
+  ];
+
+  fn symbol(self) -> &'static str {
+    match self {
+      VolumeUnit::Milliliter => "ml",
+      VolumeUnit::Liter => "l",
+      VolumeUnit::CubicMeter => "m³",
+      VolumeUnit::Teaspoon => "tsp",
+      VolumeUnit::Tablespoon => "tbsp",
+      VolumeUnit::Cup => "cup",
+      VolumeUnit::Pint => "pt",
+      VolumeUnit::Quart => "qt",
+      VolumeUnit::Gallon => "gal",
+    }
+  }
+
+  fn factor(self) -> f64 {
+    match self {
+      VolumeUnit::Milliliter => 0.001,
+      VolumeUnit::Liter => 1.0,
+      VolumeUnit::CubicMeter => 1_000.0,
+      VolumeUnit::Teaspoon => 0.004_928_921_593_75,
+      VolumeUnit::Tablespoon => 0.014_786_764_781_25,
+      VolumeUnit::Cup => 0.236_588_236_5,
+      VolumeUnit::Pint => 0.473_176_473,
+      VolumeUnit::Quart => 0.946_352_946,
+      VolumeUnit::Gallon => 3.785_411_784,
+    }
+  }
+
+  fn names(self) -> &'static [&'static str] {
+    match self {
+      VolumeUnit::Milliliter => &["milliliter", "milliliters", "millilitre", "millilitres"],
+      VolumeUnit::Liter => &["liter", "liters", "litre", "litres"],
+      VolumeUnit::CubicMeter => &["m3", "cubic meter", "cubic meters"],
+      VolumeUnit::Teaspoon => &["teaspoon", "teaspoons"],
+      VolumeUnit::Tablespoon => &["tablespoon", "tablespoons"],
+      VolumeUnit::Cup => &["cups"],
+      VolumeUnit::Pint => &["pint", "pints"],
+      VolumeUnit::Quart => &["quart", "quarts"],
+      VolumeUnit::Gallon => &["gallon", "gallons"],
+    }
+  }
+}
+
+// Base unit: the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationUnit {
+  Millisecond,
+  Second,
+  Minute,
+  Hour,
+  Day,
+  Week,
+}
+
+impl Unit for DurationUnit {
+  const DIMENSION: &'static str = "duration";
+  const ALL: &'static [DurationUnit] = &[
+    DurationUnit::Millisecond,
+    DurationUnit::Second,
+    DurationUnit::Minute,
+    DurationUnit::Hour,
+    DurationUnit::Day,
+    DurationUnit::Week,
+  ];
+
+  fn symbol(self) -> &'static str {
+    match self {
+      DurationUnit::Millisecond => "ms",
+      DurationUnit::Second => "s",
+      DurationUnit::Minute => "min",
+      DurationUnit::Hour => "h",
+      DurationUnit::Day => "d",
+      DurationUnit::Week => "wk",
+    }
+  }
+
+  fn factor(self) -> f64 {
+    match self {
+      DurationUnit::Millisecond => 0.001,
+      DurationUnit::Second => 1.0,
+      DurationUnit::Minute => 60.0,
+      DurationUnit::Hour => 3_600.0,
+      DurationUnit::Day => 86_400.0,
+      DurationUnit::Week => 604_800.0,
+    }
+  }
+
+  fn names(self) -> &'static [&'static str] {
+    match self {
+      DurationUnit::Millisecond => &["millisecond", "milliseconds"],
+      DurationUnit::Second => &["sec", "second", "seconds"],
+      DurationUnit::Minute => &["minute", "minutes"],
+      DurationUnit::Hour => &["hr", "hour", "hours"],
+      DurationUnit::Day => &["day", "days"],
+      DurationUnit::Week => &["week", "weeks"],
+    }
+  }
+}
+
+// Base unit: meters per second, so length / duration lands on it without any scaling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpeedUnit {
+  MetersPerSecond,
+  KilometersPerHour,
+  MilesPerHour,
+  FeetPerSecond,
+  Knot,
+}
+
+impl Unit for SpeedUnit {
+  const DIMENSION: &'static str = "speed";
+  const ALL: &'static [SpeedUnit] = &[
+    SpeedUnit::MetersPerSecond,
+    SpeedUnit::KilometersPerHour,
+    SpeedUnit::MilesPerHour,
+    SpeedUnit::FeetPerSecond,
+    SpeedUnit::Knot,
+  ];
+
+  fn symbol(self) -> &'static str {
+    match self {
+      SpeedUnit::MetersPerSecond => "m/s",
+      SpeedUnit::KilometersPerHour => "km/h",
+      SpeedUnit::MilesPerHour => "mph",
+      SpeedUnit::FeetPerSecond => "ft/s",
+      SpeedUnit::Knot => "kn",
+    }
+  }
+
+  fn factor(self) -> f64 {
+    match self {
+      SpeedUnit::MetersPerSecond => 1.0,
+      SpeedUnit::KilometersPerHour => 1_000.0 / 3_600.0,
+      SpeedUnit::MilesPerHour => 0.447_04,
+      SpeedUnit::FeetPerSecond => 0.3048,
+      SpeedUnit::Knot => 1_852.0 / 3_600.0,
+    }
+  }
+
+  fn names(self) -> &'static [&'static str] {
+    match self {
+      SpeedUnit::MetersPerSecond => &["meters per second"],
+      SpeedUnit::KilometersPerHour => &["kph", "kmh", "kilometers per hour"],
+      SpeedUnit::MilesPerHour => &["miles per hour"],
+      SpeedUnit::FeetPerSecond => &["fps", "feet per second"],
+      SpeedUnit::Knot => &["kt", "knot", "knots"],
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitError {
+  NotFinite,
+  UnknownUnit {
+    unit: String,
+    dimension: &'static str,
+  },
+  InvalidNumber(String),
+}
+
+impl fmt::Display for UnitError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      UnitError::NotFinite => write!(f, "a quantity has to be a finite number"),
+      UnitError::UnknownUnit { unit, dimension } => {
+        write!(f, "unknown {dimension} unit `{unit}`")
+      }
+      UnitError::InvalidNumber(number) => write!(f, "`{number}` is not a number"),
+    }
+  }
+}
+
+impl std::error::Error for UnitError {}
+
+/// An amount of something in a particular unit. The unit type fixes the dimension, so only
+/// quantities of the same dimension can be added or compared:
+///
+/// ```compile_fail
+/// use practices::units::{Duration, DurationUnit, Length, LengthUnit};
+///
+/// let distance = Length::new(100.0, LengthUnit::Meter).unwrap();
+/// let time = Duration::new(9.58, DurationUnit::Second).unwrap();
+/// let nonsense = distance + time;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Quantity<U: Unit> {
+  value: f64,
+  unit: U,
+}
+
+pub type Length = Quantity<LengthUnit>;
+pub type Mass = Quantity<MassUnit>;
+pub type Volume = Quantity<VolumeUnit>;
+pub type Duration = Quantity<DurationUnit>;
+pub type Speed = Quantity<SpeedUnit>;
+
+impl<U: Unit> Quantity<U> {
+  pub fn new(value: f64, unit: U) -> Result<Quantity<U>, UnitError> {
+    if !value.is_finite() {
+      return Err(UnitError::NotFinite);
+    }
+    Ok(Quantity { value, unit })
+  }
+
+  pub fn value(&self) -> f64 {
+    self.value
+  }
+
+  pub fn unit(&self) -> U {
+    self.unit
+  }
+
+  // The same amount in the dimension's base unit.
+  pub fn base_value(&self) -> f64 {
+    self.unit.to_base(self.value)
+  }
+
+  pub fn to(&self, unit: U) -> Quantity<U> {
+    Quantity {
+      value: unit.value_from_base(self.base_value()),
+      unit,
+    }
+  }
+}
+
+pub fn convert<U: Unit>(value: f64, from: U, to: U) -> Result<f64, UnitError> {
+  Ok(Quantity::new(value, from)?.to(to).value())
+}
+
+// Sums and differences come out in the unit of the left-hand side.
+impl<U: Unit> Add for Quantity<U> {
+  type Output = Quantity<U>;
+
+  fn add(self, other: Quantity<U>) -> Quantity<U> {
+    Quantity {
+      value: self.value + other.to(self.unit).value,
+      unit: self.unit,
+    }
+  }
+}
+
+impl<U: Unit> Sub for Quantity<U> {
+  type Output = Quantity<U>;
+
+  fn sub(self, other: Quantity<U>) -> Quantity<U> {
+    Quantity {
+      value: self.value - other.to(self.unit).value,
+      unit: self.unit,
+    }
+  }
+}
+
+impl<U: Unit> Mul<f64> for Quantity<U> {
+  type Output = Quantity<U>;
+
+  fn mul(self, factor: f64) -> Quantity<U> {
+    Quantity {
+      value: self.value * factor,
+      unit: self.unit,
+    }
+  }
+}
+
+impl<U: Unit> Div<f64> for Quantity<U> {
+  type Output = Quantity<U>;
+
+  fn div(self, divisor: f64) -> Quantity<U> {
+    Quantity {
+      value: self.value / divisor,
+      unit: self.unit,
+    }
+  }
+}
+
+// Speed is the one derived dimension, so these are the only mixed operations.
+impl Div<Duration> for Length {
+  type Output = Speed;
+
+  fn div(self, time: Duration) -> Speed {
+    Quantity {
+      value: self.base_value() / time.base_value(),
+      unit: SpeedUnit::MetersPerSecond,
+    }
+  }
+}
+
+impl Mul<Duration> for Speed {
+  type Output = Length;
+
+  fn mul(self, time: Duration) -> Length {
+    Quantity {
+      value: self.base_value() * time.base_value(),
+      unit: LengthUnit::Meter,
+    }
+  }
+}
+
+impl Div<Speed> for Length {
+  type Output = Duration;
+
+  fn div(self, speed: Speed) -> Duration {
+    Quantity {
+      value: self.base_value() / speed.base_value(),
+      unit: DurationUnit::Second,
+    }
+  }
+}
+
+impl<U: Unit> PartialEq for Quantity<U> {
+  // the same amount is equal whatever unit it's in, so 1 km == 1000 m, to agree with the
+  // ordering below. Converted values may not be, see `temperature_converter::display_value`.
+  fn eq(&self, other: &Quantity<U>) -> bool {
+    self.base_value() == other.base_value()
+  }
+}
+
+impl<U: Unit> PartialOrd for Quantity<U> {
+  // compared by amount, so 1 km > 900 m
+  fn partial_cmp(&self, other: &Quantity<U>) -> Option<std::cmp::Ordering> {
+    self.base_value().partial_cmp(&other.base_value())
+  }
+}
+
+impl<U: Unit> fmt::Display for Quantity<U> {
+  // "5 km", "60 mph"; a precision like `{:.1}` is applied to the number
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match f.precision() {
+      Some(precision) => write!(f, "{:.*} {}", precision, self.value, self.unit.symbol()),
+      None => write!(f, "{} {}", self.value, self.unit.symbol()),
+    }
+  }
+}
+
+impl<U: Unit> FromStr for Quantity<U> {
+  type Err = UnitError;
+
+  // "5km", "5.5 km", "60 miles per hour"
+  fn from_str(input: &str) -> Result<Quantity<U>, UnitError> {
+    let input = input.trim();
+    let (number, unit) = split_number(input);
+    let value: f64 = number
+      .trim()
+      .parse()
+      .map_err(|_| UnitError::InvalidNumber(input.to_string()))?;
+    Quantity::new(value, U::parse(unit)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+  }
+
+  #[test]
+  fn converts_within_a_dimension() {
+    let marathon = Length::new(42.195, LengthUnit::Kilometer).unwrap();
+    assert!(close(
+      marathon.to(LengthUnit::Mile).value(),
+      26.218_757_456_454_37
+    ));
+    assert!(close(
+      convert(1.0, LengthUnit::Foot, LengthUnit::Inch).unwrap(),
+      12.0
+    ));
+    assert!(close(
+      convert(1.0, MassUnit::Stone, MassUnit::Pound).unwrap(),
+      14.0
+    ));
+    assert!(close(
+      convert(1.0, VolumeUnit::Gallon, VolumeUnit::Cup).unwrap(),
+      16.0
+    ));
+    assert!(close(
+      convert(1.0, DurationUnit::Week, DurationUnit::Hour).unwrap(),
+      168.0
+    ));
+    assert!(close(
+      convert(
+        36.0,
+        SpeedUnit::KilometersPerHour,
+        SpeedUnit::MetersPerSecond
+      )
+      .unwrap(),
+      10.0
+    ));
+    assert_eq!(
+      convert(f64::INFINITY, MassUnit::Gram, MassUnit::Kilogram),
+      Err(UnitError::NotFinite)
+    );
+
+    fn round_trips<U: Unit>() {
+      for &from in U::ALL {
+        for &to in U::ALL {
+          let there = convert(42.0, from, to).unwrap();
+          assert!(
+            (convert(there, to, from).unwrap() - 42.0).abs() < 1e-9,
+            "{from:?} -> {to:?}"
+          );
+        }
+      }
+    }
+    round_trips::<LengthUnit>();
+    round_trips::<MassUnit>();
+    round_trips::<VolumeUnit>();
+    round_trips::<DurationUnit>();
+    round_trips::<SpeedUnit>();
+  }
+
+  #[test]
+  fn arithmetic_keeps_dimensions_apart() {
+    let run = Length::new(5.0, LengthUnit::Kilometer).unwrap()
+      + Length::new(500.0, LengthUnit::Meter).unwrap();
+    assert_eq!(run, Length::new(5.5, LengthUnit::Kilometer).unwrap());
+    assert!(
+      Length::new(1.0, LengthUnit::Kilometer).unwrap()
+        > Length::new(900.0, LengthUnit::Meter).unwrap()
+    );
+    assert_eq!(
+      Length::new(1.0, LengthUnit::Kilometer).unwrap(),
+      Length::new(1000.0, LengthUnit::Meter).unwrap()
+    );
+
+    let time = Duration::new(30.0, DurationUnit::Minute).unwrap();
+    let pace = run / time;
+    assert!(close(pace.to(SpeedUnit::KilometersPerHour).value(), 11.0));
+    assert!(close((pace * time).to(LengthUnit::Kilometer).value(), 5.5));
+    assert!(close((run / pace).to(DurationUnit::Minute).value(), 30.0));
+    assert!(close((time * 2.0 - time / 2.0).value(), 45.0));
+  }
+
+  #[test]
+  fn parses_and_displays_quantities() {
+    let height: Length = "6ft".parse().unwrap();
+    assert_eq!(height, Length::new(6.0, LengthUnit::Foot).unwrap());
+    assert_eq!(height.to_string(), "6 ft");
+    assert_eq!(format!("{:.2}", height.to(LengthUnit::Meter)), "1.83 m");
+    assert_eq!(
+      "60 miles per hour".parse::<Speed>().unwrap().unit(),
+      SpeedUnit::MilesPerHour
+    );
+    assert_eq!(
+      "2 Litres".parse::<Volume>().unwrap().unit(),
+      VolumeUnit::Liter
+    );
+    assert_eq!("1.5e3 g".parse::<Mass>().unwrap().value(), 1_500.0);
+
+    assert_eq!(
+      "3 parsecs".parse::<Length>(),
+      Err(UnitError::UnknownUnit {
+        unit: String::from("parsecs"),
+        dimension: "length"
+      })
+    );
+    // a unit of the wrong dimension is just an unknown unit
+    assert!("5 kg".parse::<Length>().is_err());
+    assert_eq!(
+      "far".parse::<Length>(),
+      Err(UnitError::InvalidNumber(String::from("far")))
+    );
+
+    for &unit in MassUnit::ALL {
+      let mass = Mass::new(12.25, unit).unwrap();
+      assert_eq!(mass.to_string().parse::<Mass>(), Ok(mass));
+    }
+  }
+}
@@ -0,0 +1,86 @@
+// One evening at the restaurant: a booking, walk-ins on the waitlist, the kitchen, a remake
+// and the check. Run it with `cargo run --example dinner`.
+use restaurant::{
+    billing::{Check, TaxRule},
+    clock::{Date, Time},
+    cook_order, deliver_when_ready, fix_incorrect_order,
+    hosting::{self, Floor, Waitlist},
+    kitchen::{Kitchen, Station},
+    menu::Menu,
+    reservations::{self, ReservationBook},
+    serving, total_customer_size,
+};
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    restaurant::eat_at_restaurant()?;
+    println!();
+
+    let tonight = Date::ymd(2024, 7, 15);
+    let arrived = Time::hm(18, 30);
+    let mut floor = Floor::with_capacity(&[(1, 2), (2, 4), (3, 4)], 8)?;
+    let menu = Menu::standard();
+
+    // one party booked ahead for later tonight
+    let mut book = ReservationBook::new();
+    let margaret = book
+        .book(&floor, "Margaret", 4, tonight, Time::hm(20, 0))?
+        .id;
+    for reservation in book.tonight(tonight) {
+        println!("reserved: {reservation}");
+    }
+
+    let mut waitlist = Waitlist::new();
+    hosting::add_to_waitlist(&mut waitlist, "Ada", 2, arrived)?;
+    hosting::add_to_waitlist(&mut waitlist, "Grace", 4, arrived)?;
+    for seating in hosting::seat_at_table(&mut waitlist, &mut floor, arrived) {
+        println!("{} sits at table {}", seating.party.name, seating.table);
+    }
+
+    // Ada's two appetizers; the kitchen cooks them and the waiter takes them out once every
+    // station is done
+    let items = ["Soup", "Salad"]
+        .iter()
+        .filter_map(|name| menu.find(name))
+        .map(|item| item.line_item(1))
+        .collect();
+    let mut order = serving::take_order(1, 1, items)?;
+    let mut kitchen = Kitchen::new();
+    cook_order(&mut kitchen, &order, &menu, arrived)?;
+    let done = arrived.plus_minutes(10);
+    for station in Station::ALL {
+        while kitchen.complete_next(station, done).is_some() {}
+    }
+    if deliver_when_ready(&kitchen, &mut order)? {
+        println!("order {} is {}", order.id(), order.state());
+    }
+    // the salad was wrong, so it's made again before anything else
+    let salad = &order.items()[1..];
+    fix_incorrect_order(&mut kitchen, &order, salad, &menu, done)?;
+    kitchen.complete_next(Station::Cold, done.plus_minutes(4));
+    let times = kitchen.ticket_times();
+    println!(
+        "{} tickets, {} remade, {} minutes on average",
+        times.tickets,
+        times.remakes,
+        times.average_minutes.unwrap_or(0)
+    );
+
+    // the check, with an 18% tip
+    let check = Check::new(&order, &menu, &[TaxRule::new("Sales tax", 875)]);
+    print!("{}", check.receipt(None));
+    let tip = check.tip_suggestions(&[18])[0].1;
+    let payment = check.pay(&mut order, tip, check.total() + tip)?;
+    println!("paid {} with a {} tip", payment.total, payment.tip);
+
+    // Grace's party leaves the table Margaret booked just before she arrives
+    let eight = Time::hm(20, 0);
+    floor.clear_table(2, Time::hm(19, 45))?;
+    let seating = reservations::seat_reservation(&mut book, &mut floor, margaret, tonight, eight)?;
+    println!("{} sits at table {}", seating.party.name, seating.table);
+    println!(
+        "currently {} people are dining",
+        total_customer_size(&floor)
+    );
+    Ok(())
+}
@@ -0,0 +1,116 @@
+pub mod kitchen;
+pub mod menu;
+
+use crate::clock::{Date, Time};
+use crate::front_of_house::serving::{LineItem, Order, OrderError};
+use kitchen::{Kitchen, KitchenError};
+use menu::{Menu, MenuItem, Season};
+
+// Something went out wrong: the kitchen remakes `items` before anything else, and once it's
+// done the order goes back out like any other.
+pub fn fix_incorrect_order(
+  kitchen: &mut Kitchen,
+  order: &Order,
+  items: &[LineItem],
+  menu: &Menu,
+  now: Time,
+) -> Result<Vec<u32>, KitchenError> {
+  kitchen.queue_remake(order, items, menu, now)
+}
+
+// Queues an order that was sent to the kitchen, one ticket per station, and returns the ticket ids.
+pub fn cook_order(
+  kitchen: &mut Kitchen,
+  order: &Order,
+  menu: &Menu,
+  now: Time,
+) -> Result<Vec<u32>, KitchenError> {
+  kitchen.queue_order(order, menu, now)
+}
+
+// Takes the order out once every ticket for it is done, false if it isn't ready yet.
+pub fn deliver_when_ready(kitchen: &Kitchen, order: &mut Order) -> Result<bool, OrderError> {
+  if !kitchen.is_ready(order.id()) {
+    return Ok(false);
+  }
+  super::deliver_order(order)?;
+  Ok(true)
+}
+
+// Making structs and enums public
+// We can also use pub to designate structs and enums as public, but there are a few extra details. If we use pub before a struct definition, we make the struct public,
+// but the struct's fields will still be private.
+pub struct Breakfast {
+  pub toast: String,
+  seasonal_fruit: String,
+}
+impl Breakfast {
+  // Becase back_of_house::Breakfast has a private field, the struct needs to provide a public associated function that constructs an instance of Breakfast (summer)
+  // if Breakfast didn't have such a function, we couldn't create an instance of Breakfast in eat_at_restaurant because we couldn't set the value of the private
+  // seasonal_fruit field
+  pub fn summer(toast: &str) -> Breakfast {
+    Breakfast::in_season(Season::Summer, toast)
+  }
+
+  // The kitchen picks the fruit from whatever is in season on `date`.
+  pub fn on(date: Date, toast: &str) -> Breakfast {
+    Breakfast::in_season(Season::of(date), toast)
+  }
+
+  fn in_season(season: Season, toast: &str) -> Breakfast {
+    Breakfast {
+      toast: String::from(toast),
+      seasonal_fruit: String::from(season.fruit()),
+    }
+  }
+
+  // The fruit still can't be changed from outside, but it does show up on the plate.
+  pub fn plate(&self) -> String {
+    format!("{} toast with {}", self.toast, self.seasonal_fruit)
+  }
+}
+
+// If we make an enum public, all of its variants are then public
+pub enum Appetizer {
+  Soup,
+  Salad,
+}
+
+impl Appetizer {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Appetizer::Soup => "Soup",
+      Appetizer::Salad => "Salad",
+    }
+  }
+
+  // Prices live on the menu, not in the code.
+  pub fn menu_item<'a>(&self, menu: &'a Menu) -> Option<&'a MenuItem> {
+    menu.find(self.name())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn breakfast_comes_with_fruit_in_season() {
+    assert_eq!(Breakfast::summer("Rye").plate(), "Rye toast with peaches");
+    let mut meal = Breakfast::on(Date::ymd(2024, 10, 3), "Rye");
+    meal.toast = String::from("Wheat");
+    assert_eq!(meal.plate(), "Wheat toast with apples");
+    assert_eq!(
+      Breakfast::on(Date::ymd(2025, 4, 1), "Sourdough").plate(),
+      "Sourdough toast with strawberries"
+    );
+  }
+
+  #[test]
+  fn appetizers_are_priced_from_the_menu() {
+    let menu = Menu::standard();
+    assert_eq!(Appetizer::Soup.menu_item(&menu).unwrap().price_cents, 550);
+    assert_eq!(Appetizer::Salad.menu_item(&menu).unwrap().price_cents, 800);
+    assert!(Appetizer::Soup.menu_item(&Menu::new()).is_none());
+  }
+}
@@ -0,0 +1,484 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  fmt,
+  str::FromStr,
+};
+
+use super::menu::Menu;
+use crate::clock::Time;
+use crate::front_of_house::serving::{LineItem, Order, OrderState};
+
+// Every order is split into one ticket per station, so the grill can start on the burgers
+// while the fryer does the fries. Each station works through its own queue in order, except
+// that remakes of dishes that went out wrong jump ahead of everything that isn't a remake.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Station {
+  Grill,
+  Fryer,
+  // salads, desserts and drinks, anything that isn't cooked to order
+  Cold,
+}
+
+impl Station {
+  pub const ALL: [Station; 3] = [Station::Grill, Station::Fryer, Station::Cold];
+}
+
+impl fmt::Display for Station {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Station::Grill => "grill",
+      Station::Fryer => "fryer",
+      Station::Cold => "cold",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for Station {
+  type Err = String;
+
+  fn from_str(input: &str) -> Result<Station, String> {
+    match input.trim().to_lowercase().as_str() {
+      "grill" => Ok(Station::Grill),
+      "fryer" => Ok(Station::Fryer),
+      "cold" => Ok(Station::Cold),
+      _ => Err(format!(
+        "unknown station `{input}`, expected grill, fryer or cold"
+      )),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KitchenError {
+  // the kitchen only cooks orders that were sent to it, and remakes ones that went out
+  WrongState { order: u32, state: OrderState },
+  NotOnMenu(String),
+  NotOnOrder { order: u32, item: String },
+  // a remake can't be for more of a dish than the table ordered
+  MoreThanOrdered { order: u32, item: String, ordered: u32 },
+  NothingToCook(u32),
+}
+
+impl fmt::Display for KitchenError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      KitchenError::WrongState { order, state } => {
+        write!(f, "order {order} is {state}, the kitchen can't cook it")
+      }
+      KitchenError::NotOnMenu(name) => write!(f, "{name} isn't on the menu"),
+      KitchenError::NotOnOrder { order, item } => write!(f, "order {order} has no {item}"),
+      KitchenError::MoreThanOrdered {
+        order,
+        item,
+        ordered,
+      } => write!(f, "order {order} only has {ordered}x {item}"),
+      KitchenError::NothingToCook(order) => write!(f, "order {order} has nothing to cook"),
+    }
+  }
+}
+
+impl std::error::Error for KitchenError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ticket {
+  pub id: u32,
+  pub order: u32,
+  pub table: u32,
+  pub station: Station,
+  pub items: Vec<LineItem>,
+  pub remake: bool,
+  pub queued_at: Time,
+}
+
+impl fmt::Display for Ticket {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let items: Vec<String> = self
+      .items
+      .iter()
+      .map(|item| format!("{}x {}", item.quantity, item.name))
+      .collect();
+    write!(
+      f,
+      "#{} table {} {}: {}",
+      self.id,
+      self.table,
+      self.station,
+      items.join(", ")
+    )?;
+    if self.remake {
+      write!(f, " (remake)")?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedTicket {
+  pub ticket: Ticket,
+  pub done_at: Time,
+}
+
+impl CompletedTicket {
+  // From being queued to being done, waiting included, and going around midnight.
+  pub fn minutes(&self) -> u32 {
+    self.ticket.queued_at.minutes_until_wrapping(self.done_at)
+  }
+}
+
+// How long tickets took, for managers. `average_minutes` is rounded to the nearest minute
+// and `None` until a ticket has been done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TicketTimes {
+  pub tickets: usize,
+  pub remakes: usize,
+  pub average_minutes: Option<u32>,
+  pub longest_minutes: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Kitchen {
+  queues: HashMap<Station, VecDeque<Ticket>>,
+  completed: Vec<CompletedTicket>,
+  next_id: u32,
+}
+
+impl Kitchen {
+  pub fn new() -> Kitchen {
+    Kitchen::default()
+  }
+
+  // Splits an order that was sent to the kitchen into tickets and returns their ids.
+  pub fn queue_order(
+    &mut self,
+    order: &Order,
+    menu: &Menu,
+    now: Time,
+  ) -> Result<Vec<u32>, KitchenError> {
+    if order.state() != OrderState::InKitchen {
+      return Err(KitchenError::WrongState {
+        order: order.id(),
+        state: order.state(),
+      });
+    }
+    self.queue(order, order.items(), menu, false, now)
+  }
+
+  // Cooks `items` from the order again, ahead of every ticket that isn't a remake. The order
+  // can still be in the kitchen if the mistake was caught at the pass.
+  pub fn queue_remake(
+    &mut self,
+    order: &Order,
+    items: &[LineItem],
+    menu: &Menu,
+    now: Time,
+  ) -> Result<Vec<u32>, KitchenError> {
+    if !matches!(order.state(), OrderState::InKitchen | OrderState::Served) {
+      return Err(KitchenError::WrongState {
+        order: order.id(),
+        state: order.state(),
+      });
+    }
+    let quantity = |items: &[LineItem], name: &str| -> u32 {
+      items
+        .iter()
+        .filter(|item| item.name == name)
+        .map(|item| item.quantity)
+        .sum()
+    };
+    for item in items {
+      let ordered = quantity(order.items(), &item.name);
+      if ordered == 0 {
+        return Err(KitchenError::NotOnOrder {
+          order: order.id(),
+          item: item.name.clone(),
+        });
+      }
+      if quantity(items, &item.name) > ordered {
+        return Err(KitchenError::MoreThanOrdered {
+          order: order.id(),
+          item: item.name.clone(),
+          ordered,
+        });
+      }
+    }
+    self.queue(order, items, menu, true, now)
+  }
+
+  fn queue(
+    &mut self,
+    order: &Order,
+    items: &[LineItem],
+    menu: &Menu,
+    remake: bool,
+    now: Time,
+  ) -> Result<Vec<u32>, KitchenError> {
+    // work out every station first so a bad item doesn't leave half an order queued
+    let mut by_station: Vec<(Station, Vec<LineItem>)> = Vec::new();
+    for item in items.iter().filter(|item| item.quantity > 0) {
+      let station = menu
+        .find(&item.name)
+        .ok_or_else(|| KitchenError::NotOnMenu(item.name.clone()))?
+        .station;
+      match by_station
+        .iter_mut()
+        .find(|(existing, _)| *existing == station)
+      {
+        Some((_, station_items)) => station_items.push(item.clone()),
+        None => by_station.push((station, vec![item.clone()])),
+      }
+    }
+    if by_station.is_empty() {
+      return Err(KitchenError::NothingToCook(order.id()));
+    }
+
+    let mut ids = Vec::new();
+    for (station, items) in by_station {
+      self.next_id += 1;
+      let ticket = Ticket {
+        id: self.next_id,
+        order: order.id(),
+        table: order.table(),
+        station,
+        items,
+        remake,
+        queued_at: now,
+      };
+      let queue = self.queues.entry(station).or_default();
+      // a remake goes behind the remakes already waiting, but ahead of everything else
+      let at = if remake {
+        queue
+          .iter()
+          .position(|waiting| !waiting.remake)
+          .unwrap_or(queue.len())
+      } else {
+        queue.len()
+      };
+      queue.insert(at, ticket);
+      ids.push(self.next_id);
+    }
+    Ok(ids)
+  }
+
+  // The tickets waiting at `station`, the next one to cook first.
+  pub fn queue_at(&self, station: Station) -> impl Iterator<Item = &Ticket> {
+    self.queues.get(&station).into_iter().flatten()
+  }
+
+  pub fn pending(&self) -> usize {
+    self.queues.values().map(VecDeque::len).sum()
+  }
+
+  // The station finished its next ticket, `None` if it had nothing to do.
+  pub fn complete_next(&mut self, station: Station, now: Time) -> Option<&CompletedTicket> {
+    let ticket = self.queues.get_mut(&station)?.pop_front()?;
+    self.completed.push(CompletedTicket {
+      ticket,
+      done_at: now,
+    });
+    self.completed.last()
+  }
+
+  pub fn completed(&self) -> &[CompletedTicket] {
+    &self.completed
+  }
+
+  // Everything for the order has been cooked and nothing for it is waiting.
+  pub fn is_ready(&self, order: u32) -> bool {
+    self.completed.iter().any(|done| done.ticket.order == order)
+      && !self
+        .queues
+        .values()
+        .flatten()
+        .any(|ticket| ticket.order == order)
+  }
+
+  pub fn ticket_times(&self) -> TicketTimes {
+    times(self.completed.iter())
+  }
+
+  pub fn ticket_times_at(&self, station: Station) -> TicketTimes {
+    times(
+      self
+        .completed
+        .iter()
+        .filter(|done| done.ticket.station == station),
+    )
+  }
+}
+
+fn times<'a>(completed: impl Iterator<Item = &'a CompletedTicket>) -> TicketTimes {
+  let mut times = TicketTimes {
+    tickets: 0,
+    remakes: 0,
+    average_minutes: None,
+    longest_minutes: 0,
+  };
+  let mut total = 0;
+  for done in completed {
+    times.tickets += 1;
+    if done.ticket.remake {
+      times.remakes += 1;
+    }
+    total += done.minutes();
+    times.longest_minutes = times.longest_minutes.max(done.minutes());
+  }
+  if times.tickets > 0 {
+    let tickets = times.tickets as u32;
+    times.average_minutes = Some((total + tickets / 2) / tickets);
+  }
+  times
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::front_of_house::serving::take_order;
+
+  fn burger_and_fries(id: u32) -> Order {
+    let menu = Menu::standard();
+    let items = ["Burger", "Fries", "Lemonade", "Burger"]
+      .iter()
+      .map(|name| menu.find(name).unwrap().line_item(1))
+      .collect();
+    take_order(id, 3, items).unwrap()
+  }
+
+  #[test]
+  fn splits_orders_into_tickets_per_station() {
+    let menu = Menu::standard();
+    let mut kitchen = Kitchen::new();
+    let ids = kitchen
+      .queue_order(&burger_and_fries(1), &menu, Time::hm(18, 0))
+      .unwrap();
+    assert_eq!(ids, [1, 2, 3]);
+
+    let grill: Vec<String> = kitchen
+      .queue_at(Station::Grill)
+      .map(Ticket::to_string)
+      .collect();
+    assert_eq!(grill, ["#1 table 3 grill: 2x Burger"]);
+    assert_eq!(kitchen.queue_at(Station::Fryer).count(), 1);
+    assert_eq!(kitchen.pending(), 3);
+    assert!(!kitchen.is_ready(1));
+
+    for station in Station::ALL {
+      kitchen.complete_next(station, Time::hm(18, 12));
+    }
+    assert!(kitchen.is_ready(1));
+    assert!(kitchen
+      .complete_next(Station::Grill, Time::hm(18, 13))
+      .is_none());
+  }
+
+  #[test]
+  fn only_cooks_what_it_can() {
+    let menu = Menu::standard();
+    let mut kitchen = Kitchen::new();
+    let mut order = Order::new(7, 1);
+    order.add_item(LineItem::new("Burger", 1, 1400)).unwrap();
+    assert_eq!(
+      kitchen.queue_order(&order, &menu, Time::hm(18, 0)),
+      Err(KitchenError::WrongState {
+        order: 7,
+        state: OrderState::Taken
+      })
+    );
+
+    let order = take_order(8, 1, vec![LineItem::new("Lobster", 1, 4000)]).unwrap();
+    assert_eq!(
+      kitchen.queue_order(&order, &menu, Time::hm(18, 0)),
+      Err(KitchenError::NotOnMenu(String::from("Lobster")))
+    );
+    assert_eq!(kitchen.pending(), 0);
+  }
+
+  #[test]
+  fn remakes_jump_the_queue() {
+    let menu = Menu::standard();
+    let mut kitchen = Kitchen::new();
+    let mut first = burger_and_fries(1);
+    kitchen.queue_order(&first, &menu, Time::hm(18, 0)).unwrap();
+    kitchen
+      .queue_order(&burger_and_fries(2), &menu, Time::hm(18, 1))
+      .unwrap();
+    first.serve().unwrap();
+
+    let remake = [LineItem::new("Burger", 1, 1400)];
+    kitchen
+      .queue_remake(&first, &remake, &menu, Time::hm(18, 20))
+      .unwrap();
+    let grill: Vec<(u32, bool)> = kitchen
+      .queue_at(Station::Grill)
+      .map(|ticket| (ticket.order, ticket.remake))
+      .collect();
+    assert_eq!(grill, [(1, true), (1, false), (2, false)]);
+
+    assert_eq!(
+      kitchen.queue_remake(
+        &first,
+        &[LineItem::new("Salad", 1, 800)],
+        &menu,
+        Time::hm(18, 21)
+      ),
+      Err(KitchenError::NotOnOrder {
+        order: 1,
+        item: String::from("Salad")
+      })
+    );
+    assert_eq!(
+      kitchen.queue_remake(
+        &first,
+        &[LineItem::new("Fries", 2, 400)],
+        &menu,
+        Time::hm(18, 21)
+      ),
+      Err(KitchenError::MoreThanOrdered {
+        order: 1,
+        item: String::from("Fries"),
+        ordered: 1
+      })
+    );
+  }
+
+  #[test]
+  fn keeps_ticket_times() {
+    let menu = Menu::standard();
+    let mut kitchen = Kitchen::new();
+    assert_eq!(kitchen.ticket_times().average_minutes, None);
+
+    kitchen
+      .queue_order(&burger_and_fries(1), &menu, Time::hm(18, 0))
+      .unwrap();
+    kitchen.complete_next(Station::Cold, Time::hm(18, 3));
+    kitchen.complete_next(Station::Fryer, Time::hm(18, 8));
+    kitchen.complete_next(Station::Grill, Time::hm(18, 14));
+
+    let times = kitchen.ticket_times();
+    assert_eq!(times.tickets, 3);
+    assert_eq!(times.average_minutes, Some(8));
+    assert_eq!(times.longest_minutes, 14);
+    assert_eq!(
+      kitchen.ticket_times_at(Station::Fryer).average_minutes,
+      Some(8)
+    );
+    assert_eq!(kitchen.ticket_times_at(Station::Grill).remakes, 0);
+  }
+
+  #[test]
+  fn ticket_times_go_around_midnight() {
+    let menu = Menu::standard();
+    let mut kitchen = Kitchen::new();
+    let late = Time::hm(23, 55);
+    kitchen
+      .queue_order(&burger_and_fries(1), &menu, late)
+      .unwrap();
+    kitchen.complete_next(Station::Cold, Time::hm(0, 5));
+    kitchen.complete_next(Station::Grill, late.plus_minutes(12));
+
+    let times = kitchen.ticket_times();
+    assert_eq!(times.longest_minutes, 12);
+    assert_eq!(kitchen.completed()[0].minutes(), 10);
+  }
+}
@@ -0,0 +1,636 @@
+use std::{fmt, fs, io, path::Path, str::FromStr};
+
+use super::kitchen::Station;
+use crate::clock::Date;
+use crate::front_of_house::serving::LineItem;
+
+// What's on the menu, with prices in cents so they add up exactly. It's usually read from a
+// file with a small subset of TOML, see menu.toml at the root of the crate:
+//
+//   [[item]]
+//   name = "Soup"
+//   category = "appetizer"
+//   price = "5.50"
+//   tags = ["vegan", "gluten-free"]
+//   seasons = ["autumn", "winter"]
+//   station = "grill"
+//
+// `tags`, `seasons` and `station` are optional, an item without seasons is served all year
+// and one without a station is cooked where its category usually is. Only
+// strings, bare prices and one-line arrays of strings are understood.
+
+// Seasons go by month the way the kitchen's suppliers do, winter is December to February.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Season {
+  Spring,
+  Summer,
+  Autumn,
+  Winter,
+}
+
+impl Season {
+  pub fn of(date: Date) -> Season {
+    match date.month() {
+      3..=5 => Season::Spring,
+      6..=8 => Season::Summer,
+      9..=11 => Season::Autumn,
+      _ => Season::Winter,
+    }
+  }
+
+  // The fruit that comes with breakfast this time of year.
+  pub fn fruit(self) -> &'static str {
+    match self {
+      Season::Spring => "strawberries",
+      Season::Summer => "peaches",
+      Season::Autumn => "apples",
+      Season::Winter => "oranges",
+    }
+  }
+}
+
+impl fmt::Display for Season {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Season::Spring => "spring",
+      Season::Summer => "summer",
+      Season::Autumn => "autumn",
+      Season::Winter => "winter",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for Season {
+  type Err = String;
+
+  fn from_str(input: &str) -> Result<Season, String> {
+    match input.trim().to_lowercase().as_str() {
+      "spring" => Ok(Season::Spring),
+      "summer" => Ok(Season::Summer),
+      "autumn" | "fall" => Ok(Season::Autumn),
+      "winter" => Ok(Season::Winter),
+      _ => Err(format!("unknown season `{input}`")),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+  Breakfast,
+  Appetizer,
+  Main,
+  Dessert,
+  Drink,
+}
+
+impl Category {
+  // Where the kitchen cooks things in this category unless the menu says otherwise.
+  pub fn station(self) -> Station {
+    match self {
+      Category::Breakfast | Category::Main => Station::Grill,
+      Category::Appetizer | Category::Dessert | Category::Drink => Station::Cold,
+    }
+  }
+}
+
+impl fmt::Display for Category {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Category::Breakfast => "breakfast",
+      Category::Appetizer => "appetizer",
+      Category::Main => "main",
+      Category::Dessert => "dessert",
+      Category::Drink => "drink",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for Category {
+  type Err = String;
+
+  fn from_str(input: &str) -> Result<Category, String> {
+    match input.trim().to_lowercase().as_str() {
+      "breakfast" => Ok(Category::Breakfast),
+      "appetizer" => Ok(Category::Appetizer),
+      "main" => Ok(Category::Main),
+      "dessert" => Ok(Category::Dessert),
+      "drink" => Ok(Category::Drink),
+      _ => Err(format!("unknown category `{input}`")),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DietaryTag {
+  Vegetarian,
+  Vegan,
+  GlutenFree,
+  DairyFree,
+  NutFree,
+  Spicy,
+}
+
+impl fmt::Display for DietaryTag {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      DietaryTag::Vegetarian => "vegetarian",
+      DietaryTag::Vegan => "vegan",
+      DietaryTag::GlutenFree => "gluten-free",
+      DietaryTag::DairyFree => "dairy-free",
+      DietaryTag::NutFree => "nut-free",
+      DietaryTag::Spicy => "spicy",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for DietaryTag {
+  type Err = String;
+
+  fn from_str(input: &str) -> Result<DietaryTag, String> {
+    match input.trim().to_lowercase().as_str() {
+      "vegetarian" => Ok(DietaryTag::Vegetarian),
+      "vegan" => Ok(DietaryTag::Vegan),
+      "gluten-free" => Ok(DietaryTag::GlutenFree),
+      "dairy-free" => Ok(DietaryTag::DairyFree),
+      "nut-free" => Ok(DietaryTag::NutFree),
+      "spicy" => Ok(DietaryTag::Spicy),
+      _ => Err(format!("unknown dietary tag `{input}`")),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+  pub name: String,
+  pub category: Category,
+  pub price_cents: u64,
+  pub tags: Vec<DietaryTag>,
+  // empty means all year
+  pub seasons: Vec<Season>,
+  pub station: Station,
+}
+
+impl MenuItem {
+  pub fn new(name: &str, category: Category, price_cents: u64) -> MenuItem {
+    MenuItem {
+      name: String::from(name),
+      category,
+      price_cents,
+      tags: Vec::new(),
+      seasons: Vec::new(),
+      station: category.station(),
+    }
+  }
+
+  pub fn is_available_on(&self, date: Date) -> bool {
+    self.seasons.is_empty() || self.seasons.contains(&Season::of(date))
+  }
+
+  // Vegan food is vegetarian too, even if the menu only says vegan.
+  pub fn has_tag(&self, tag: DietaryTag) -> bool {
+    self.tags.contains(&tag)
+      || (tag == DietaryTag::Vegetarian && self.tags.contains(&DietaryTag::Vegan))
+  }
+
+  // `quantity` of this item for an order, at the menu price.
+  pub fn line_item(&self, quantity: u32) -> LineItem {
+    LineItem::new(&self.name, quantity, self.price_cents)
+  }
+}
+
+impl fmt::Display for MenuItem {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} {}", self.name, format_price(self.price_cents))?;
+    if !self.tags.is_empty() {
+      let tags: Vec<String> = self.tags.iter().map(DietaryTag::to_string).collect();
+      write!(f, " ({})", tags.join(", "))?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug)]
+pub enum MenuError {
+  Io(io::Error),
+  // `line` counts from 1 like an editor does
+  Parse { line: usize, message: String },
+  DuplicateItem(String),
+}
+
+impl fmt::Display for MenuError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      MenuError::Io(error) => write!(f, "{error}"),
+      MenuError::Parse { line, message } => write!(f, "line {line}: {message}"),
+      MenuError::DuplicateItem(name) => write!(f, "{name} is already on the menu"),
+    }
+  }
+}
+
+impl std::error::Error for MenuError {}
+
+impl From<io::Error> for MenuError {
+  fn from(error: io::Error) -> MenuError {
+    MenuError::Io(error)
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Menu {
+  items: Vec<MenuItem>,
+}
+
+impl Menu {
+  pub fn new() -> Menu {
+    Menu::default()
+  }
+
+  // The menu the restaurant ships with, menu.toml at the root of the crate.
+  pub fn standard() -> Menu {
+    Menu::parse(include_str!("../../menu.toml")).expect("menu.toml is a valid menu")
+  }
+
+  pub fn load<P: AsRef<Path>>(path: P) -> Result<Menu, MenuError> {
+    Menu::parse(&fs::read_to_string(path)?)
+  }
+
+  pub fn parse(text: &str) -> Result<Menu, MenuError> {
+    let mut menu = Menu::new();
+    let mut draft: Option<Draft> = None;
+    for (index, line) in text.lines().enumerate() {
+      let number = index + 1;
+      let line = strip_comment(line).trim();
+      if line.is_empty() {
+        continue;
+      }
+      if line == "[[item]]" {
+        if let Some(finished) = draft.take() {
+          menu.add_draft(finished)?;
+        }
+        draft = Some(Draft::new(number));
+        continue;
+      }
+
+      let parse_error = |message: String| MenuError::Parse {
+        line: number,
+        message,
+      };
+      let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| parse_error(String::from("expected `key = value` or [[item]]")))?;
+      let current = draft
+        .as_mut()
+        .ok_or_else(|| parse_error(String::from("keys have to come after an [[item]]")))?;
+      current.set(key.trim(), value.trim()).map_err(parse_error)?;
+    }
+    if let Some(finished) = draft {
+      menu.add_draft(finished)?;
+    }
+    Ok(menu)
+  }
+
+  fn add_draft(&mut self, draft: Draft) -> Result<(), MenuError> {
+    let line = draft.line;
+    let item = draft
+      .finish()
+      .map_err(|message| MenuError::Parse { line, message })?;
+    self.add(item).map_err(|error| MenuError::Parse {
+      line,
+      message: error.to_string(),
+    })
+  }
+
+  // Names are unique, ignoring case, so an order can refer to an item by name.
+  pub fn add(&mut self, item: MenuItem) -> Result<(), MenuError> {
+    if self.find(&item.name).is_some() {
+      return Err(MenuError::DuplicateItem(item.name));
+    }
+    self.items.push(item);
+    Ok(())
+  }
+
+  pub fn items(&self) -> &[MenuItem] {
+    &self.items
+  }
+
+  pub fn find(&self, name: &str) -> Option<&MenuItem> {
+    self
+      .items
+      .iter()
+      .find(|item| item.name.eq_ignore_ascii_case(name.trim()))
+  }
+
+  // Everything that can be ordered on `date`, in menu order.
+  pub fn available_on(&self, date: Date) -> Vec<&MenuItem> {
+    self
+      .items
+      .iter()
+      .filter(|item| item.is_available_on(date))
+      .collect()
+  }
+
+  pub fn in_category(&self, category: Category, date: Date) -> Vec<&MenuItem> {
+    self
+      .available_on(date)
+      .into_iter()
+      .filter(|item| item.category == category)
+      .collect()
+  }
+
+  pub fn with_tag(&self, tag: DietaryTag, date: Date) -> Vec<&MenuItem> {
+    self
+      .available_on(date)
+      .into_iter()
+      .filter(|item| item.has_tag(tag))
+      .collect()
+  }
+}
+
+// "$9.50"
+pub fn format_price(cents: u64) -> String {
+  format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+// "9.50", "9.5" or "9" dollars, in cents. No floats, so 0.10 + 0.20 really is 0.30.
+pub fn parse_price(input: &str) -> Option<u64> {
+  let (dollars, cents) = input.trim().split_once('.').unwrap_or((input.trim(), ""));
+  let all_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+  if dollars.is_empty() || !all_digits(dollars) || cents.len() > 2 || !all_digits(cents) {
+    return None;
+  }
+  let cents = match cents.len() {
+    0 => 0,
+    1 => cents.parse::<u64>().ok()? * 10,
+    _ => cents.parse().ok()?,
+  };
+  dollars
+    .parse::<u64>()
+    .ok()?
+    .checked_mul(100)?
+    .checked_add(cents)
+}
+
+// An [[item]] as it's being read, `line` is where it started so errors can point at it.
+struct Draft {
+  line: usize,
+  name: Option<String>,
+  category: Option<Category>,
+  price_cents: Option<u64>,
+  tags: Option<Vec<DietaryTag>>,
+  seasons: Option<Vec<Season>>,
+  station: Option<Station>,
+}
+
+impl Draft {
+  fn new(line: usize) -> Draft {
+    Draft {
+      line,
+      name: None,
+      category: None,
+      price_cents: None,
+      tags: None,
+      seasons: None,
+      station: None,
+    }
+  }
+
+  fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+    let twice = || format!("`{key}` is set twice for the same item");
+    match key {
+      "name" if self.name.is_some() => Err(twice()),
+      "name" => {
+        let name = parse_string(value)?;
+        if name.trim().is_empty() {
+          return Err(String::from("an item's name can't be empty"));
+        }
+        self.name = Some(name);
+        Ok(())
+      }
+      "category" if self.category.is_some() => Err(twice()),
+      "category" => {
+        self.category = Some(parse_string(value)?.parse()?);
+        Ok(())
+      }
+      "price" if self.price_cents.is_some() => Err(twice()),
+      "price" => {
+        // "9.50" and 9.50 both work, TOML people write it either way
+        let price = if value.starts_with('"') {
+          parse_string(value)?
+        } else {
+          String::from(value)
+        };
+        let cents =
+          parse_price(&price).ok_or_else(|| format!("`{price}` isn't a price like 9.50"))?;
+        self.price_cents = Some(cents);
+        Ok(())
+      }
+      "tags" if self.tags.is_some() => Err(twice()),
+      "tags" => {
+        self.tags = Some(parse_list(value)?);
+        Ok(())
+      }
+      "seasons" if self.seasons.is_some() => Err(twice()),
+      "seasons" => {
+        self.seasons = Some(parse_list(value)?);
+        Ok(())
+      }
+      "station" if self.station.is_some() => Err(twice()),
+      "station" => {
+        self.station = Some(parse_string(value)?.parse()?);
+        Ok(())
+      }
+      _ => Err(format!("unknown key `{key}`")),
+    }
+  }
+
+  fn finish(self) -> Result<MenuItem, String> {
+    let missing = |key: &str| format!("the item has no `{key}`");
+    let category = self.category.ok_or_else(|| missing("category"))?;
+    Ok(MenuItem {
+      name: self.name.ok_or_else(|| missing("name"))?,
+      category,
+      price_cents: self.price_cents.ok_or_else(|| missing("price"))?,
+      tags: self.tags.unwrap_or_default(),
+      seasons: self.seasons.unwrap_or_default(),
+      station: self.station.unwrap_or(category.station()),
+    })
+  }
+}
+
+// Drops a `#` comment, unless the `#` is inside a string.
+fn strip_comment(line: &str) -> &str {
+  let mut in_string = false;
+  let mut escaped = false;
+  for (index, c) in line.char_indices() {
+    match c {
+      _ if escaped => escaped = false,
+      '\\' if in_string => escaped = true,
+      '"' => in_string = !in_string,
+      '#' if !in_string => return &line[..index],
+      _ => {}
+    }
+  }
+  line
+}
+
+// A quoted string that has to be all of `value`.
+fn parse_string(value: &str) -> Result<String, String> {
+  let (string, rest) = take_string(value)?;
+  if !rest.trim().is_empty() {
+    return Err(format!("unexpected `{}` after the string", rest.trim()));
+  }
+  Ok(string)
+}
+
+// Reads the quoted string at the start of `value` and returns it with whatever follows it.
+fn take_string(value: &str) -> Result<(String, &str), String> {
+  let body = value
+    .strip_prefix('"')
+    .ok_or_else(|| format!("expected a quoted string, found `{value}`"))?;
+  let mut string = String::new();
+  let mut chars = body.char_indices();
+  while let Some((index, c)) = chars.next() {
+    match c {
+      '"' => return Ok((string, &body[index + 1..])),
+      '\\' => match chars.next() {
+        Some((_, '"')) => string.push('"'),
+        Some((_, '\\')) => string.push('\\'),
+        Some((_, 'n')) => string.push('\n'),
+        Some((_, 't')) => string.push('\t'),
+        Some((_, other)) => return Err(format!("unknown escape `\\{other}`")),
+        None => break,
+      },
+      c => string.push(c),
+    }
+  }
+  Err(format!("the string {value} is never closed"))
+}
+
+// ["vegan", "gluten-free"], a trailing comma is fine.
+fn parse_list<T: FromStr<Err = String>>(value: &str) -> Result<Vec<T>, String> {
+  let mut rest = value
+    .strip_prefix('[')
+    .ok_or_else(|| format!("expected a list like [\"a\", \"b\"], found `{value}`"))?
+    .trim_start();
+  let mut list = Vec::new();
+  loop {
+    if let Some(after) = rest.strip_prefix(']') {
+      if !after.trim().is_empty() {
+        return Err(format!("unexpected `{}` after the list", after.trim()));
+      }
+      return Ok(list);
+    }
+    let (string, after) = take_string(rest)?;
+    list.push(string.parse()?);
+    let after = after.trim_start();
+    rest = match after.strip_prefix(',') {
+      Some(after) => after.trim_start(),
+      None if after.starts_with(']') => after,
+      None => return Err(String::from("expected `,` or `]` in the list")),
+    };
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_prices_exactly() {
+    assert_eq!(parse_price("9.50"), Some(950));
+    assert_eq!(parse_price("9.5"), Some(950));
+    assert_eq!(parse_price("12"), Some(1200));
+    assert_eq!(parse_price("0.05"), Some(5));
+    assert_eq!(parse_price("9.505"), None);
+    assert_eq!(parse_price("-1.00"), None);
+    assert_eq!(parse_price(".50"), None);
+    assert_eq!(format_price(1875), "$18.75");
+    assert_eq!(format_price(5), "$0.05");
+  }
+
+  #[test]
+  fn reads_a_menu_file() {
+    let menu = Menu::parse(
+      "# specials\n\
+       [[item]]\n\
+       name = \"Soup #1\"  # the comment starts here\n\
+       category = \"appetizer\"\n\
+       price = 5.50\n\
+       tags = [\"vegan\", \"gluten-free\",]\n\
+       \n\
+       [[item]]\n\
+       name = \"Chili\"\n\
+       category = \"main\"\n\
+       price = \"12.5\"\n\
+       seasons = [\"fall\", \"winter\"]\n\
+       station = \"fryer\"\n",
+    )
+    .unwrap();
+
+    let soup = menu.find("soup #1").unwrap();
+    assert_eq!(soup.price_cents, 550);
+    assert!(soup.has_tag(DietaryTag::Vegetarian));
+    assert_eq!(soup.to_string(), "Soup #1 $5.50 (vegan, gluten-free)");
+    assert_eq!(
+      menu.find("Chili").unwrap().seasons,
+      [Season::Autumn, Season::Winter]
+    );
+    assert_eq!(soup.station, Station::Cold);
+    assert_eq!(menu.find("Chili").unwrap().station, Station::Fryer);
+    assert_eq!(soup.line_item(2).total_cents(), 1100);
+  }
+
+  #[test]
+  fn points_at_the_line_with_the_mistake() {
+    let error = |text: &str| match Menu::parse(text) {
+      Err(MenuError::Parse { line, message }) => (line, message),
+      other => panic!("expected a parse error, got {other:?}"),
+    };
+    assert_eq!(
+      error("name = \"Soup\""),
+      (1, String::from("keys have to come after an [[item]]"))
+    );
+    assert_eq!(
+      error("[[item]]\nname = \"Soup\"\ncategory = \"soup\""),
+      (3, String::from("unknown category `soup`"))
+    );
+    assert_eq!(
+      error("[[item]]\nname = \"Soup\"\ncategory = \"main\""),
+      (1, String::from("the item has no `price`"))
+    );
+    assert_eq!(
+      error("[[item]]\nname = \"Soup\"\ncategory = \"main\"\nprice = 1\n[[item]]\nname = \"SOUP\"\ncategory = \"main\"\nprice = 2"),
+      (5, String::from("SOUP is already on the menu"))
+    );
+    assert_eq!(
+      error("[[item]]\ntags = [\"vegan\" \"spicy\"]"),
+      (2, String::from("expected `,` or `]` in the list"))
+    );
+  }
+
+  #[test]
+  fn the_menu_changes_with_the_seasons() {
+    let menu = Menu::standard();
+    let summer = Date::ymd(2024, 7, 15);
+    let winter = Date::ymd(2024, 12, 24);
+
+    let desserts = |date| -> Vec<&str> {
+      menu
+        .in_category(Category::Dessert, date)
+        .into_iter()
+        .map(|item| item.name.as_str())
+        .collect()
+    };
+    assert_eq!(desserts(summer), ["Peach Cobbler", "Ice Cream"]);
+    assert_eq!(desserts(winter), ["Ice Cream"]);
+    assert!(menu
+      .with_tag(DietaryTag::Spicy, winter)
+      .iter()
+      .any(|item| item.name == "Chili"));
+    assert!(menu.with_tag(DietaryTag::Spicy, summer).is_empty());
+    assert_eq!(Season::of(Date::ymd(2024, 2, 29)).fruit(), "oranges");
+  }
+}
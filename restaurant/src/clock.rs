@@ -0,0 +1,221 @@
+use std::{fmt, str::FromStr};
+
+pub const MINUTES_PER_DAY: u32 = 24 * 60;
+
+// A time of day to the minute, which is all a restaurant needs. It's stored as minutes since
+// midnight and isn't tied to the system clock, so callers pass "now" in explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+  minutes: u32,
+}
+
+impl Time {
+  // Panics if the hour or minute is out of range, use `parse` for times that come from users.
+  pub fn hm(hour: u32, minute: u32) -> Time {
+    assert!(
+      hour < 24 && minute < 60,
+      "{hour}:{minute:02} isn't a time of day"
+    );
+    Time {
+      minutes: hour * 60 + minute,
+    }
+  }
+
+  pub fn from_minutes(minutes: u32) -> Time {
+    Time { minutes }
+  }
+
+  pub fn minutes(self) -> u32 {
+    self.minutes
+  }
+
+  pub fn hour(self) -> u32 {
+    self.minutes / 60 % 24
+  }
+
+  pub fn minute(self) -> u32 {
+    self.minutes % 60
+  }
+
+  // This doesn't wrap at midnight: 23:30 plus an hour shows as 00:30 but still sorts after
+  // 23:30, which is what a booking or a ticket that runs into the night wants.
+  pub fn plus_minutes(self, minutes: u32) -> Time {
+    Time {
+      minutes: self.minutes + minutes,
+    }
+  }
+
+  // How long after `earlier` this is, 0 if it's actually before it.
+  pub fn minutes_since(self, earlier: Time) -> u32 {
+    self.minutes.saturating_sub(earlier.minutes)
+  }
+
+  // How long from this time until `later`, going around midnight when `later` is earlier in
+  // the day, so 23:30 until 00:15 is 45 minutes. Only for things that take less than a day.
+  pub fn minutes_until_wrapping(self, later: Time) -> u32 {
+    let from = self.minutes % MINUTES_PER_DAY;
+    let to = later.minutes % MINUTES_PER_DAY;
+    (to + MINUTES_PER_DAY - from) % MINUTES_PER_DAY
+  }
+}
+
+impl fmt::Display for Time {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:02}:{:02}", self.hour(), self.minute())
+  }
+}
+
+impl FromStr for Time {
+  type Err = String;
+
+  // "18:30", "9:05"
+  fn from_str(input: &str) -> Result<Time, String> {
+    let invalid = || format!("`{input}` isn't a time, expected something like 18:30");
+    let (hour, minute) = input.trim().split_once(':').ok_or_else(invalid)?;
+    // only digits, `parse` alone would let "+9:05" through
+    let digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if !(1..=2).contains(&hour.len()) || minute.len() != 2 || !digits(hour) || !digits(minute) {
+      return Err(invalid());
+    }
+    let hour: u32 = hour.parse().map_err(|_| invalid())?;
+    let minute: u32 = minute.parse().map_err(|_| invalid())?;
+    if hour >= 24 || minute >= 60 {
+      return Err(invalid());
+    }
+    Ok(Time::hm(hour, minute))
+  }
+}
+
+// A calendar day, for things like the menu changing with the seasons. Like `Time` it never
+// looks at the system clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+  year: i32,
+  month: u32,
+  day: u32,
+}
+
+impl Date {
+  // Panics if there's no such day, use `parse` for dates that come from users.
+  pub fn ymd(year: i32, month: u32, day: u32) -> Date {
+    assert!(
+      (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day),
+      "{year}-{month:02}-{day:02} isn't a date"
+    );
+    Date { year, month, day }
+  }
+
+  pub fn year(self) -> i32 {
+    self.year
+  }
+
+  // 1 for January through 12 for December
+  pub fn month(self) -> u32 {
+    self.month
+  }
+
+  pub fn day(self) -> u32 {
+    self.day
+  }
+
+  pub fn next_day(self) -> Date {
+    if self.day < days_in_month(self.year, self.month) {
+      Date::ymd(self.year, self.month, self.day + 1)
+    } else if self.month < 12 {
+      Date::ymd(self.year, self.month + 1, 1)
+    } else {
+      Date::ymd(self.year + 1, 1, 1)
+    }
+  }
+}
+
+fn is_leap_year(year: i32) -> bool {
+  year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+  match month {
+    2 if is_leap_year(year) => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  }
+}
+
+impl fmt::Display for Date {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+  }
+}
+
+impl FromStr for Date {
+  type Err = String;
+
+  // "2024-07-15"
+  fn from_str(input: &str) -> Result<Date, String> {
+    let invalid = || format!("`{input}` isn't a date, expected something like 2024-07-15");
+    let mut parts = input.trim().splitn(3, '-');
+    let mut next = || -> Result<u32, String> {
+      parts
+        .next()
+        .and_then(|part| part.parse().ok())
+        .ok_or_else(invalid)
+    };
+    let (year, month, day) = (next()?, next()?, next()?);
+    let year = i32::try_from(year).map_err(|_| invalid())?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+      return Err(invalid());
+    }
+    Ok(Date::ymd(year, month, day))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_and_displays_times() {
+    assert_eq!("18:30".parse(), Ok(Time::hm(18, 30)));
+    assert_eq!("9:05".parse::<Time>().unwrap().to_string(), "09:05");
+    assert!("24:00".parse::<Time>().is_err());
+    assert!("18:5".parse::<Time>().is_err());
+    assert!("+9:05".parse::<Time>().is_err());
+    assert!("18:+5".parse::<Time>().is_err());
+    assert!("dinner".parse::<Time>().is_err());
+  }
+
+  #[test]
+  fn measures_minutes_between_times() {
+    let seated = Time::hm(19, 40);
+    assert_eq!(seated.plus_minutes(45), Time::hm(20, 25));
+    assert_eq!(Time::hm(20, 25).minutes_since(seated), 45);
+    assert_eq!(seated.minutes_since(Time::hm(20, 25)), 0);
+    assert_eq!(Time::hm(23, 30).plus_minutes(60).to_string(), "00:30");
+    assert_eq!(Time::hm(23, 30).minutes_until_wrapping(Time::hm(0, 15)), 45);
+    assert_eq!(seated.minutes_until_wrapping(Time::hm(20, 25)), 45);
+    assert_eq!(
+      Time::hm(23, 30).minutes_until_wrapping(Time::hm(23, 30).plus_minutes(50)),
+      50
+    );
+  }
+
+  #[test]
+  fn parses_and_displays_dates() {
+    assert_eq!("2024-07-15".parse(), Ok(Date::ymd(2024, 7, 15)));
+    assert_eq!(Date::ymd(2025, 1, 2).to_string(), "2025-01-02");
+    assert!("2024-02-29".parse::<Date>().is_ok());
+    assert!("2023-02-29".parse::<Date>().is_err());
+    assert!("1900-02-29".parse::<Date>().is_err());
+    assert!("2024-13-01".parse::<Date>().is_err());
+    assert!("tomorrow".parse::<Date>().is_err());
+  }
+
+  #[test]
+  fn finds_the_next_day() {
+    assert_eq!(Date::ymd(2024, 7, 15).next_day(), Date::ymd(2024, 7, 16));
+    assert_eq!(Date::ymd(2024, 2, 28).next_day(), Date::ymd(2024, 2, 29));
+    assert_eq!(Date::ymd(2023, 2, 28).next_day(), Date::ymd(2023, 3, 1));
+    assert_eq!(Date::ymd(2024, 12, 31).next_day(), Date::ymd(2025, 1, 1));
+  }
+}
@@ -1,13 +1,11 @@
+pub mod billing;
 pub mod hosting;
+pub mod reservations;
+pub mod serving;
 
-mod serving {
-  fn take_order() {}
+use hosting::Floor;
 
-  fn serve_order() {}
-
-  fn take_payment() {}
-}
-
-pub fn total_customer_size() -> i32 {
-  30
+// How many people are dining right now.
+pub fn total_customer_size(floor: &Floor) -> u32 {
+  floor.occupancy()
 }
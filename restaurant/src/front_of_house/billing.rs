@@ -0,0 +1,597 @@
+use std::{
+  cmp::Reverse,
+  fmt, iter,
+  ops::{Add, Mul, Sub},
+  str::FromStr,
+};
+
+use super::serving::{self, Order, OrderError};
+use crate::back_of_house::menu::{format_price, parse_price, Category, Menu};
+
+// Money is whole cents and every rate is in whole hundredths of a percent, so nothing here
+// touches a float. Wherever a cent has to be rounded it's rounded half up, and wherever a
+// total is shared out the shares always add back up to the total.
+
+pub const DEFAULT_TIP_PERCENTS: [u32; 3] = [15, 18, 20];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money {
+  cents: u64,
+}
+
+impl Money {
+  pub const ZERO: Money = Money { cents: 0 };
+
+  pub fn from_cents(cents: u64) -> Money {
+    Money { cents }
+  }
+
+  pub fn cents(self) -> u64 {
+    self.cents
+  }
+
+  // `None` if `other` is more than this.
+  pub fn checked_sub(self, other: Money) -> Option<Money> {
+    self.cents.checked_sub(other.cents).map(Money::from_cents)
+  }
+
+  // 875 basis points is 8.75%
+  pub fn basis_points(self, basis_points: u32) -> Money {
+    Money::from_cents(rounded(self.cents * u64::from(basis_points), 10_000))
+  }
+
+  pub fn percent(self, percent: u32) -> Money {
+    Money::from_cents(rounded(self.cents * u64::from(percent), 100))
+  }
+}
+
+fn rounded(numerator: u64, denominator: u64) -> u64 {
+  (numerator + denominator / 2) / denominator
+}
+
+impl Add for Money {
+  type Output = Money;
+
+  fn add(self, other: Money) -> Money {
+    Money::from_cents(self.cents + other.cents)
+  }
+}
+
+impl Sub for Money {
+  type Output = Money;
+
+  // Panics below zero like integers do, use `checked_sub` when that can happen.
+  fn sub(self, other: Money) -> Money {
+    Money::from_cents(self.cents - other.cents)
+  }
+}
+
+impl Mul<u32> for Money {
+  type Output = Money;
+
+  fn mul(self, times: u32) -> Money {
+    Money::from_cents(self.cents * u64::from(times))
+  }
+}
+
+impl iter::Sum for Money {
+  fn sum<I: Iterator<Item = Money>>(amounts: I) -> Money {
+    amounts.fold(Money::ZERO, Add::add)
+  }
+}
+
+impl fmt::Display for Money {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.pad(&format_price(self.cents))
+  }
+}
+
+impl FromStr for Money {
+  type Err = String;
+
+  // "9.50", "$9.50" or "9"
+  fn from_str(input: &str) -> Result<Money, String> {
+    let trimmed = input.trim();
+    parse_price(trimmed.strip_prefix('$').unwrap_or(trimmed))
+      .map(Money::from_cents)
+      .ok_or_else(|| format!("`{input}` isn't an amount like 9.50"))
+  }
+}
+
+// A tax on the items in `categories`, or on everything if there are none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxRule {
+  pub name: String,
+  // hundredths of a percent, 875 is 8.75%
+  pub basis_points: u32,
+  pub categories: Vec<Category>,
+}
+
+impl TaxRule {
+  pub fn new(name: &str, basis_points: u32) -> TaxRule {
+    TaxRule {
+      name: String::from(name),
+      basis_points,
+      categories: Vec::new(),
+    }
+  }
+
+  pub fn only_on(mut self, categories: &[Category]) -> TaxRule {
+    self.categories = categories.to_vec();
+    self
+  }
+
+  // Items that aren't on the menu only pay the taxes on everything.
+  pub fn applies_to(&self, category: Option<Category>) -> bool {
+    self.categories.is_empty()
+      || category.is_some_and(|category| self.categories.contains(&category))
+  }
+}
+
+impl fmt::Display for TaxRule {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let (whole, hundredths) = (self.basis_points / 100, self.basis_points % 100);
+    let rate = format!("{whole}.{hundredths:02}");
+    write!(
+      f,
+      "{} {}%",
+      self.name,
+      rate.trim_end_matches('0').trim_end_matches('.')
+    )
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BillingError {
+  NoGuests,
+  WrongOrder { check: u32, order: u32 },
+  NoSuchItem(String),
+  // splitting by item has to leave every item with someone paying for it
+  Unclaimed(String),
+  Underpaid { due: Money, tendered: Money },
+  Order(OrderError),
+}
+
+impl fmt::Display for BillingError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      BillingError::NoGuests => write!(f, "a check has to be split between at least one guest"),
+      BillingError::WrongOrder { check, order } => {
+        write!(f, "this is the check for order {check}, not order {order}")
+      }
+      BillingError::NoSuchItem(name) => write!(f, "there's no {name} on the check"),
+      BillingError::Unclaimed(name) => write!(f, "nobody is paying for the {name}"),
+      BillingError::Underpaid { due, tendered } => {
+        write!(f, "{tendered} doesn't cover the {due} that's due")
+      }
+      BillingError::Order(error) => write!(f, "{error}"),
+    }
+  }
+}
+
+impl std::error::Error for BillingError {}
+
+impl From<OrderError> for BillingError {
+  fn from(error: OrderError) -> BillingError {
+    BillingError::Order(error)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillLine {
+  pub name: String,
+  pub quantity: u32,
+  pub unit_price: Money,
+  // `None` for things that aren't on the menu
+  pub category: Option<Category>,
+}
+
+impl BillLine {
+  pub fn total(&self) -> Money {
+    self.unit_price * self.quantity
+  }
+}
+
+// One guest's part of a split check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuestShare {
+  pub guest: String,
+  pub subtotal: Money,
+  pub tax: Money,
+}
+
+impl GuestShare {
+  pub fn total(&self) -> Money {
+    self.subtotal + self.tax
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payment {
+  pub total: Money,
+  pub tip: Money,
+  pub tendered: Money,
+  pub change: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+  order: u32,
+  table: u32,
+  lines: Vec<BillLine>,
+  rules: Vec<TaxRule>,
+}
+
+impl Check {
+  // The menu says which category each item is in, so the right taxes apply to it.
+  pub fn new(order: &Order, menu: &Menu, rules: &[TaxRule]) -> Check {
+    let lines = order
+      .items()
+      .iter()
+      .map(|item| BillLine {
+        name: item.name.clone(),
+        quantity: item.quantity,
+        unit_price: Money::from_cents(item.unit_price_cents),
+        category: menu.find(&item.name).map(|found| found.category),
+      })
+      .collect();
+    Check {
+      order: order.id(),
+      table: order.table(),
+      lines,
+      rules: rules.to_vec(),
+    }
+  }
+
+  pub fn lines(&self) -> &[BillLine] {
+    &self.lines
+  }
+
+  pub fn subtotal(&self) -> Money {
+    self.lines.iter().map(BillLine::total).sum()
+  }
+
+  // Each rule with what it comes to, rounded once on everything it applies to rather than
+  // per item.
+  pub fn taxes(&self) -> Vec<(&TaxRule, Money)> {
+    self
+      .rules
+      .iter()
+      .map(|rule| {
+        let taxable: Money = self
+          .lines
+          .iter()
+          .filter(|line| rule.applies_to(line.category))
+          .map(BillLine::total)
+          .sum();
+        (rule, taxable.basis_points(rule.basis_points))
+      })
+      .collect()
+  }
+
+  pub fn tax(&self) -> Money {
+    self.taxes().into_iter().map(|(_, tax)| tax).sum()
+  }
+
+  pub fn total(&self) -> Money {
+    self.subtotal() + self.tax()
+  }
+
+  // Tips are suggested on the subtotal, nobody tips on the tax.
+  pub fn tip_suggestions(&self, percents: &[u32]) -> Vec<(u32, Money)> {
+    percents
+      .iter()
+      .map(|&percent| (percent, self.subtotal().percent(percent)))
+      .collect()
+  }
+
+  // Everyone pays the same, the cents that don't divide go to the first guests.
+  pub fn split_evenly(&self, guests: u32) -> Result<Vec<Money>, BillingError> {
+    if guests == 0 {
+      return Err(BillingError::NoGuests);
+    }
+    let shares = apportion(self.total().cents(), &vec![1; guests as usize]);
+    Ok(shares.into_iter().map(Money::from_cents).collect())
+  }
+
+  // Each claim is a guest and an item they're paying for, an item claimed by several guests
+  // is shared between them. Guests come back in the order they first claimed something.
+  pub fn split_by_item(&self, claims: &[(&str, &str)]) -> Result<Vec<GuestShare>, BillingError> {
+    let mut guests: Vec<&str> = Vec::new();
+    // for each line, the guests paying for it
+    let mut payers: Vec<Vec<usize>> = vec![Vec::new(); self.lines.len()];
+    for &(guest, item) in claims {
+      let line = self
+        .lines
+        .iter()
+        .position(|line| line.name.eq_ignore_ascii_case(item.trim()))
+        .ok_or_else(|| BillingError::NoSuchItem(String::from(item)))?;
+      let guest = match guests.iter().position(|&existing| existing == guest) {
+        Some(index) => index,
+        None => {
+          guests.push(guest);
+          guests.len() - 1
+        }
+      };
+      if !payers[line].contains(&guest) {
+        payers[line].push(guest);
+      }
+    }
+    if guests.is_empty() {
+      return Err(BillingError::NoGuests);
+    }
+    if let Some(line) = self
+      .lines
+      .iter()
+      .zip(&payers)
+      .find(|(_, payers)| payers.is_empty())
+    {
+      return Err(BillingError::Unclaimed(line.0.name.clone()));
+    }
+
+    // what each guest pays of each line, before tax
+    let shares: Vec<Vec<u64>> = self
+      .lines
+      .iter()
+      .zip(&payers)
+      .map(|(line, payers)| {
+        let split = apportion(line.total().cents(), &vec![1; payers.len()]);
+        let mut by_guest = vec![0; guests.len()];
+        for (&guest, share) in payers.iter().zip(split) {
+          by_guest[guest] = share;
+        }
+        by_guest
+      })
+      .collect();
+
+    let mut result: Vec<GuestShare> = guests
+      .iter()
+      .enumerate()
+      .map(|(guest, name)| GuestShare {
+        guest: String::from(*name),
+        subtotal: Money::from_cents(shares.iter().map(|line| line[guest]).sum()),
+        tax: Money::ZERO,
+      })
+      .collect();
+    // each tax is shared by what every guest had that it applies to
+    for (rule, tax) in self.taxes() {
+      let taxable: Vec<u64> = (0..guests.len())
+        .map(|guest| {
+          self
+            .lines
+            .iter()
+            .zip(&shares)
+            .filter(|(line, _)| rule.applies_to(line.category))
+            .map(|(_, line)| line[guest])
+            .sum()
+        })
+        .collect();
+      for (share, tax) in result.iter_mut().zip(apportion(tax.cents(), &taxable)) {
+        share.tax = share.tax + Money::from_cents(tax);
+      }
+    }
+    Ok(result)
+  }
+
+  // Settles the check: the order is marked paid and the change comes back.
+  pub fn pay(
+    &self,
+    order: &mut Order,
+    tip: Money,
+    tendered: Money,
+  ) -> Result<Payment, BillingError> {
+    if order.id() != self.order {
+      return Err(BillingError::WrongOrder {
+        check: self.order,
+        order: order.id(),
+      });
+    }
+    let due = self.total() + tip;
+    let change = tendered
+      .checked_sub(due)
+      .ok_or(BillingError::Underpaid { due, tendered })?;
+    serving::take_payment(order)?;
+    Ok(Payment {
+      total: self.total(),
+      tip,
+      tendered,
+      change,
+    })
+  }
+
+  // A plain text receipt. Without a tip it ends with the suggested ones instead.
+  pub fn receipt(&self, tip: Option<Money>) -> String {
+    let row = |label: &str, amount: Money| format!("{label:<24}{amount:>10}\n");
+    let mut receipt = format!("Order {}, table {}\n", self.order, self.table);
+    for line in &self.lines {
+      receipt += &row(&format!("{}x {}", line.quantity, line.name), line.total());
+    }
+    receipt += &row("Subtotal", self.subtotal());
+    for (rule, tax) in self.taxes() {
+      receipt += &row(&rule.to_string(), tax);
+    }
+    receipt += &row("Total", self.total());
+    match tip {
+      Some(tip) => {
+        receipt += &row("Tip", tip);
+        receipt += &row("Total with tip", self.total() + tip);
+      }
+      None => {
+        let suggestions: Vec<String> = self
+          .tip_suggestions(&DEFAULT_TIP_PERCENTS)
+          .into_iter()
+          .map(|(percent, tip)| format!("{percent}% {tip}"))
+          .collect();
+        receipt += &format!("Suggested tips: {}\n", suggestions.join(", "));
+      }
+    }
+    receipt
+  }
+}
+
+// Shares `total` out in proportion to `weights`, exactly: everyone gets their share rounded
+// down and the cents left over go to the largest remainders, the first ones on a tie.
+fn apportion(total: u64, weights: &[u64]) -> Vec<u64> {
+  let sum: u64 = weights.iter().sum();
+  if sum == 0 {
+    return vec![0; weights.len()];
+  }
+  let mut shares: Vec<u64> = weights.iter().map(|weight| total * weight / sum).collect();
+  let mut left = total - shares.iter().sum::<u64>();
+  let mut by_remainder: Vec<usize> = (0..weights.len()).collect();
+  by_remainder.sort_by_key(|&index| Reverse(total * weights[index] % sum));
+  for index in by_remainder {
+    if left == 0 {
+      break;
+    }
+    shares[index] += 1;
+    left -= 1;
+  }
+  shares
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::front_of_house::serving::{take_order, LineItem};
+
+  // 2 soups, a burger and a lemonade
+  fn lunch() -> (Order, Check) {
+    let menu = Menu::standard();
+    let order = take_order(
+      4,
+      2,
+      ["Soup", "Soup", "Burger", "Lemonade"]
+        .iter()
+        .map(|name| menu.find(name).unwrap().line_item(1))
+        .collect(),
+    )
+    .unwrap();
+    let rules = [
+      TaxRule::new("Sales tax", 875),
+      TaxRule::new("Drinks tax", 150).only_on(&[Category::Drink]),
+    ];
+    let check = Check::new(&order, &menu, &rules);
+    (order, check)
+  }
+
+  #[test]
+  fn money_is_exact() {
+    let dime: Money = "0.10".parse().unwrap();
+    let twenty: Money = "$0.20".parse().unwrap();
+    assert_eq!(dime + twenty, Money::from_cents(30));
+    assert_eq!((dime * 3).to_string(), "$0.30");
+    assert_eq!(
+      Money::from_cents(1000).basis_points(875),
+      Money::from_cents(88)
+    );
+    assert_eq!(Money::from_cents(1999).percent(15), Money::from_cents(300));
+    assert_eq!(dime.checked_sub(twenty), None);
+    assert!("ten dollars".parse::<Money>().is_err());
+  }
+
+  #[test]
+  fn taxes_follow_the_rules() {
+    let (_, check) = lunch();
+    // 5.50 + 5.50 + 14.00 + 3.25
+    assert_eq!(check.subtotal(), Money::from_cents(2825));
+    let taxes: Vec<(String, u64)> = check
+      .taxes()
+      .into_iter()
+      .map(|(rule, tax)| (rule.to_string(), tax.cents()))
+      .collect();
+    assert_eq!(
+      taxes,
+      [
+        (String::from("Sales tax 8.75%"), 247),
+        (String::from("Drinks tax 1.5%"), 5)
+      ]
+    );
+    assert_eq!(check.total(), Money::from_cents(3077));
+    assert_eq!(
+      check.tip_suggestions(&[15, 20]),
+      [(15, Money::from_cents(424)), (20, Money::from_cents(565))]
+    );
+  }
+
+  #[test]
+  fn splits_add_back_up_to_the_total() {
+    let (_, check) = lunch();
+    let even = check.split_evenly(3).unwrap();
+    assert_eq!(even, [1026, 1026, 1025].map(Money::from_cents));
+    assert_eq!(check.split_evenly(0), Err(BillingError::NoGuests));
+
+    let shares = check
+      .split_by_item(&[
+        ("Ada", "Soup"),
+        ("Grace", "soup"),
+        ("Grace", "Burger"),
+        ("Ada", "Lemonade"),
+      ])
+      .unwrap();
+    assert_eq!(shares[0].guest, "Ada");
+    assert_eq!(shares[0].subtotal, Money::from_cents(550 + 325));
+    assert_eq!(shares[1].subtotal, Money::from_cents(550 + 1400));
+    // only Ada pays the drinks tax
+    assert_eq!(shares[0].tax, Money::from_cents(77 + 5));
+    assert_eq!(shares[1].tax, Money::from_cents(170));
+    assert_eq!(
+      shares.iter().map(GuestShare::total).sum::<Money>(),
+      check.total()
+    );
+
+    assert_eq!(
+      check.split_by_item(&[("Ada", "Soup")]),
+      Err(BillingError::Unclaimed(String::from("Burger")))
+    );
+    assert_eq!(
+      check.split_by_item(&[("Ada", "Pie")]),
+      Err(BillingError::NoSuchItem(String::from("Pie")))
+    );
+  }
+
+  #[test]
+  fn paying_settles_the_order() {
+    let (mut order, check) = lunch();
+    serving::serve_order(&mut order).unwrap();
+    let tip = Money::from_cents(500);
+    assert_eq!(
+      check.pay(&mut order, tip, Money::from_cents(3000)),
+      Err(BillingError::Underpaid {
+        due: Money::from_cents(3577),
+        tendered: Money::from_cents(3000)
+      })
+    );
+
+    let payment = check.pay(&mut order, tip, Money::from_cents(4000)).unwrap();
+    assert_eq!(payment.change, Money::from_cents(423));
+    assert_eq!(order.state(), serving::OrderState::Paid);
+
+    let mut other = take_order(5, 1, vec![LineItem::new("Soup", 1, 550)]).unwrap();
+    assert_eq!(
+      check.pay(&mut other, tip, Money::from_cents(4000)),
+      Err(BillingError::WrongOrder { check: 4, order: 5 })
+    );
+  }
+
+  #[test]
+  fn prints_a_receipt() {
+    let (_, check) = lunch();
+    assert_eq!(
+      check.receipt(Some(Money::from_cents(500))),
+      "Order 4, table 2\n\
+       2x Soup                     $11.00\n\
+       1x Burger                   $14.00\n\
+       1x Lemonade                  $3.25\n\
+       Subtotal                    $28.25\n\
+       Sales tax 8.75%              $2.47\n\
+       Drinks tax 1.5%              $0.05\n\
+       Total                       $30.77\n\
+       Tip                          $5.00\n\
+       Total with tip              $35.77\n"
+    );
+    assert!(check
+      .receipt(None)
+      .ends_with("Suggested tips: 15% $4.24, 18% $5.09, 20% $5.65\n"));
+  }
+}
@@ -1,3 +1,692 @@
-pub fn add_to_waitlist() {}
+use crate::clock::Time;
+use std::{collections::VecDeque, fmt};
 
-fn seat_at_table() {}
+// How long a table is assumed to stay taken before there's any turnover to go by.
+pub const DEFAULT_TURNOVER_MINUTES: u32 = 45;
+// Wait estimates use the average of this many of the most recent table turnovers.
+pub const TURNOVER_HISTORY: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostingError {
+  EmptyParty,
+  AlreadyWaiting(String),
+  NotWaiting(String),
+  // no table in the restaurant is big enough, however long they wait
+  TooLarge { name: String, size: u32 },
+  NoSuchTable(u32),
+  TableIsFree(u32),
+  TableIsTaken(u32),
+  TableTooSmall { table: u32, size: u32 },
+  DuplicateTable(u32),
+  NoFreeTable { name: String, size: u32 },
+  // seating the party would put more people inside than the fire code allows
+  OverCapacity {
+    name: String,
+    size: u32,
+    occupancy: u32,
+    capacity: u32,
+  },
+}
+
+impl fmt::Display for HostingError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      HostingError::EmptyParty => write!(f, "a party needs at least one person"),
+      HostingError::AlreadyWaiting(name) => write!(f, "{name} is already on the waitlist"),
+      HostingError::NotWaiting(name) => write!(f, "{name} isn't on the waitlist"),
+      HostingError::TooLarge { name, size } => {
+        write!(f, "no table seats {name}'s party of {size}")
+      }
+      HostingError::NoSuchTable(number) => write!(f, "there's no table {number}"),
+      HostingError::TableIsFree(number) => write!(f, "nobody is sitting at table {number}"),
+      HostingError::TableIsTaken(number) => write!(f, "somebody is still at table {number}"),
+      HostingError::TableTooSmall { table, size } => {
+        write!(f, "table {table} doesn't seat a party of {size}")
+      }
+      HostingError::DuplicateTable(number) => write!(f, "there are two tables numbered {number}"),
+      HostingError::NoFreeTable { name, size } => {
+        write!(f, "no free table fits {name}'s party of {size} right now")
+      }
+      HostingError::OverCapacity {
+        name,
+        size,
+        occupancy,
+        capacity,
+      } => write!(
+        f,
+        "seating {name}'s party of {size} would put {} people inside, the limit is {capacity}",
+        occupancy + size
+      ),
+    }
+  }
+}
+
+impl std::error::Error for HostingError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Party {
+  pub name: String,
+  pub size: u32,
+  pub arrived: Time,
+}
+
+// Parties waiting for a table, in the order they arrived.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Waitlist {
+  parties: VecDeque<Party>,
+}
+
+impl Waitlist {
+  pub fn new() -> Waitlist {
+    Waitlist::default()
+  }
+
+  // Returns the party's place in line, 1 being next.
+  pub fn add(&mut self, name: &str, size: u32, arrived: Time) -> Result<usize, HostingError> {
+    if size == 0 {
+      return Err(HostingError::EmptyParty);
+    }
+    if self.position(name).is_some() {
+      return Err(HostingError::AlreadyWaiting(String::from(name)));
+    }
+    self.parties.push_back(Party {
+      name: String::from(name),
+      size,
+      arrived,
+    });
+    Ok(self.parties.len())
+  }
+
+  // For parties that give up and leave.
+  pub fn remove(&mut self, name: &str) -> Result<Party, HostingError> {
+    let index = self
+      .parties
+      .iter()
+      .position(|party| party.name == name)
+      .ok_or_else(|| HostingError::NotWaiting(String::from(name)))?;
+    Ok(
+      self
+        .parties
+        .remove(index)
+        .expect("the index was just found"),
+    )
+  }
+
+  // 1 for the party at the front of the line.
+  pub fn position(&self, name: &str) -> Option<usize> {
+    self
+      .parties
+      .iter()
+      .position(|party| party.name == name)
+      .map(|index| index + 1)
+  }
+
+  pub fn parties(&self) -> impl Iterator<Item = &Party> {
+    self.parties.iter()
+  }
+
+  pub fn len(&self) -> usize {
+    self.parties.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.parties.is_empty()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+  pub number: u32,
+  pub seats: u32,
+  // who's sitting there and since when
+  pub seated: Option<(Party, Time)>,
+}
+
+impl Table {
+  pub fn is_free(&self) -> bool {
+    self.seated.is_none()
+  }
+}
+
+// A party that couldn't be seated, handed back with the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refusal {
+  pub party: Party,
+  pub reason: HostingError,
+}
+
+// Where a party ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seating {
+  pub party: Party,
+  pub table: u32,
+  pub at: Time,
+}
+
+// The dining room: every table, who's at it, and how long recent parties stayed. It also
+// keeps count of the people inside, which can't go over the venue's capacity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Floor {
+  tables: Vec<Table>,
+  turnovers: VecDeque<u32>,
+  capacity: u32,
+  peak_occupancy: u32,
+  guests_served: u32,
+}
+
+impl Floor {
+  // Takes (table number, seats) pairs, every number used once. The capacity is every seat
+  // being taken, use `with_capacity` when the fire code allows fewer people than that.
+  pub fn new(tables: &[(u32, u32)]) -> Result<Floor, HostingError> {
+    let seats = tables.iter().map(|&(_, seats)| seats).sum();
+    Floor::with_capacity(tables, seats)
+  }
+
+  pub fn with_capacity(tables: &[(u32, u32)], capacity: u32) -> Result<Floor, HostingError> {
+    for (index, &(number, _)) in tables.iter().enumerate() {
+      if tables[..index].iter().any(|&(other, _)| other == number) {
+        return Err(HostingError::DuplicateTable(number));
+      }
+    }
+    Ok(Floor {
+      tables: tables
+        .iter()
+        .map(|&(number, seats)| Table {
+          number,
+          seats,
+          seated: None,
+        })
+        .collect(),
+      turnovers: VecDeque::new(),
+      capacity,
+      peak_occupancy: 0,
+      guests_served: 0,
+    })
+  }
+
+  pub fn capacity(&self) -> u32 {
+    self.capacity
+  }
+
+  // Lowering the capacity doesn't send anyone home, it only stops new parties from sitting.
+  pub fn set_capacity(&mut self, capacity: u32) {
+    self.capacity = capacity;
+  }
+
+  // How many people are sitting at tables right now.
+  pub fn occupancy(&self) -> u32 {
+    self
+      .tables
+      .iter()
+      .filter_map(|table| table.seated.as_ref())
+      .map(|(party, _)| party.size)
+      .sum()
+  }
+
+  pub fn available_capacity(&self) -> u32 {
+    self.capacity.saturating_sub(self.occupancy())
+  }
+
+  // The most people that were inside at once.
+  pub fn peak_occupancy(&self) -> u32 {
+    self.peak_occupancy
+  }
+
+  // Everyone who has eaten and left.
+  pub fn guests_served(&self) -> u32 {
+    self.guests_served
+  }
+
+  pub fn tables(&self) -> &[Table] {
+    &self.tables
+  }
+
+  pub fn largest_table(&self) -> u32 {
+    self
+      .tables
+      .iter()
+      .map(|table| table.seats)
+      .max()
+      .unwrap_or(0)
+  }
+
+  // The smallest free table the party fits at, so big tables stay open for big parties.
+  pub fn best_fit(&self, size: u32) -> Option<u32> {
+    best_fit(
+      self
+        .tables
+        .iter()
+        .filter(|table| table.is_free())
+        .map(|table| (table.number, table.seats)),
+      size,
+    )
+  }
+
+  // Seats the party at the best fitting free table. The party is handed back if no free table
+  // fits it or if it would take the room over capacity.
+  pub fn seat(&mut self, party: Party, now: Time) -> Result<Seating, Refusal> {
+    if let Some(reason) = self.over_capacity(&party) {
+      return Err(Refusal { party, reason });
+    }
+    match self.best_fit(party.size) {
+      Some(number) => self.seat_at(party, number, now),
+      None => {
+        let reason = HostingError::NoFreeTable {
+          name: party.name.clone(),
+          size: party.size,
+        };
+        Err(Refusal { party, reason })
+      }
+    }
+  }
+
+  // Seats the party at table `number`, like a reservation that was given a table when it was
+  // booked. Handed back if that table is taken or too small, or the room is full.
+  pub fn seat_at(&mut self, party: Party, number: u32, now: Time) -> Result<Seating, Refusal> {
+    if let Some(reason) = self.over_capacity(&party) {
+      return Err(Refusal { party, reason });
+    }
+    let occupancy = self.occupancy();
+    let table = match self.tables.iter_mut().find(|table| table.number == number) {
+      Some(table) => table,
+      None => {
+        let reason = HostingError::NoSuchTable(number);
+        return Err(Refusal { party, reason });
+      }
+    };
+    if !table.is_free() {
+      let reason = HostingError::TableIsTaken(number);
+      return Err(Refusal { party, reason });
+    }
+    if table.seats < party.size {
+      let reason = HostingError::TableTooSmall {
+        table: number,
+        size: party.size,
+      };
+      return Err(Refusal { party, reason });
+    }
+    table.seated = Some((party.clone(), now));
+    self.peak_occupancy = self.peak_occupancy.max(occupancy + party.size);
+    Ok(Seating {
+      party,
+      table: number,
+      at: now,
+    })
+  }
+
+  fn over_capacity(&self, party: &Party) -> Option<HostingError> {
+    let occupancy = self.occupancy();
+    if occupancy + party.size <= self.capacity {
+      return None;
+    }
+    Some(HostingError::OverCapacity {
+      name: party.name.clone(),
+      size: party.size,
+      occupancy,
+      capacity: self.capacity,
+    })
+  }
+
+  // The party at `number` leaves. How long they stayed feeds the wait estimates.
+  pub fn clear_table(&mut self, number: u32, now: Time) -> Result<Party, HostingError> {
+    let table = self
+      .tables
+      .iter_mut()
+      .find(|table| table.number == number)
+      .ok_or(HostingError::NoSuchTable(number))?;
+    let (party, seated_at) = table
+      .seated
+      .take()
+      .ok_or(HostingError::TableIsFree(number))?;
+
+    self.guests_served += party.size;
+    self
+      .turnovers
+      .push_back(seated_at.minutes_until_wrapping(now));
+    if self.turnovers.len() > TURNOVER_HISTORY {
+      self.turnovers.pop_front();
+    }
+    Ok(party)
+  }
+
+  // How long a table stays taken, on average over the last few parties.
+  pub fn average_turnover(&self) -> u32 {
+    if self.turnovers.is_empty() {
+      return DEFAULT_TURNOVER_MINUTES;
+    }
+    let total: u32 = self.turnovers.iter().sum();
+    // rounded to the nearest minute
+    (total + self.turnovers.len() as u32 / 2) / self.turnovers.len() as u32
+  }
+}
+
+// Out of some (table number, seats) pairs, the smallest table that fits `size`. Ties go to the
+// lower number.
+fn best_fit(tables: impl Iterator<Item = (u32, u32)>, size: u32) -> Option<u32> {
+  tables
+    .filter(|&(_, seats)| seats >= size)
+    .min_by_key(|&(number, seats)| (seats, number))
+    .map(|(number, _)| number)
+}
+
+pub fn add_to_waitlist(
+  waitlist: &mut Waitlist,
+  name: &str,
+  size: u32,
+  arrived: Time,
+) -> Result<usize, HostingError> {
+  waitlist.add(name, size, arrived)
+}
+
+// Seats everyone on the waitlist that a free table fits, first come first served. A party
+// that doesn't fit anywhere yet, or would take the room over capacity, keeps its place, but
+// smaller parties behind it can still be seated.
+pub fn seat_at_table(waitlist: &mut Waitlist, floor: &mut Floor, now: Time) -> Vec<Seating> {
+  let mut seated = Vec::new();
+  let mut still_waiting = VecDeque::new();
+
+  while let Some(party) = waitlist.parties.pop_front() {
+    match floor.seat(party, now) {
+      Ok(seating) => seated.push(seating),
+      Err(refusal) => still_waiting.push_back(refusal.party),
+    }
+  }
+
+  waitlist.parties = still_waiting;
+  seated
+}
+
+// Roughly how many minutes until `name` gets a table. Occupied tables are expected to free
+// up once they've been taken for the average turnover, and everyone ahead in line is seated
+// as soon as a table that fits them frees up, at the best fit of the tables free by then just
+// like `Floor::seat` does. So this is only as good as the recent turnover.
+pub fn estimate_wait(
+  waitlist: &Waitlist,
+  floor: &Floor,
+  name: &str,
+  now: Time,
+) -> Result<u32, HostingError> {
+  let position = waitlist
+    .position(name)
+    .ok_or_else(|| HostingError::NotWaiting(String::from(name)))?;
+  let turnover = floor.average_turnover();
+  // when each table is expected to be free next, counted on from `now` so a table taken
+  // before midnight frees up a few minutes after a `now` that's past it
+  let mut free_at: Vec<(u32, u32, Time)> = floor
+    .tables
+    .iter()
+    .map(|table| {
+      let free = match &table.seated {
+        Some((_, seated_at)) => {
+          let taken_for = seated_at.minutes_until_wrapping(now);
+          now.plus_minutes(turnover.saturating_sub(taken_for))
+        }
+        None => now,
+      };
+      (table.number, table.seats, free)
+    })
+    .collect();
+
+  for party in waitlist.parties.iter().take(position) {
+    let seated_at = free_at
+      .iter()
+      .filter(|&&(_, seats, _)| seats >= party.size)
+      .map(|&(_, _, free)| free)
+      .min();
+    let seated_at = match seated_at {
+      Some(seated_at) => seated_at,
+      None if party.name == name => {
+        return Err(HostingError::TooLarge {
+          name: party.name.clone(),
+          size: party.size,
+        })
+      }
+      // a party that can never be seated doesn't hold anyone else up
+      None => continue,
+    };
+
+    if party.name == name {
+      return Ok(seated_at.minutes_since(now));
+    }
+    let free_by_then = free_at
+      .iter()
+      .filter(|&&(_, _, free)| free <= seated_at)
+      .map(|&(number, seats, _)| (number, seats));
+    let number = best_fit(free_by_then, party.size).expect("a table that fits is free by then");
+    let table = free_at
+      .iter_mut()
+      .find(|(other, _, _)| *other == number)
+      .expect("best_fit only returns existing tables");
+    table.2 = seated_at.plus_minutes(turnover);
+  }
+
+  unreachable!("{name} was found on the waitlist")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn party(name: &str, size: u32) -> Party {
+    Party {
+      name: String::from(name),
+      size,
+      arrived: Time::hm(18, 0),
+    }
+  }
+
+  #[test]
+  fn the_waitlist_keeps_arrival_order() {
+    let mut waitlist = Waitlist::new();
+    assert_eq!(
+      add_to_waitlist(&mut waitlist, "Ada", 2, Time::hm(18, 0)),
+      Ok(1)
+    );
+    assert_eq!(
+      add_to_waitlist(&mut waitlist, "Grace", 4, Time::hm(18, 5)),
+      Ok(2)
+    );
+    assert_eq!(
+      waitlist.add("Ada", 3, Time::hm(18, 6)),
+      Err(HostingError::AlreadyWaiting(String::from("Ada")))
+    );
+    assert_eq!(
+      waitlist.add("Nobody", 0, Time::hm(18, 6)),
+      Err(HostingError::EmptyParty)
+    );
+
+    assert_eq!(waitlist.remove("Ada").unwrap().size, 2);
+    assert_eq!(waitlist.position("Grace"), Some(1));
+    assert!(waitlist.remove("Ada").is_err());
+  }
+
+  #[test]
+  fn parties_get_the_smallest_table_they_fit() {
+    let mut floor = Floor::new(&[(1, 6), (2, 2), (3, 4)]).unwrap();
+    let mut waitlist = Waitlist::new();
+    waitlist.add("Eight", 8, Time::hm(18, 0)).unwrap();
+    waitlist.add("Three", 3, Time::hm(18, 1)).unwrap();
+    waitlist.add("Two", 2, Time::hm(18, 2)).unwrap();
+    waitlist.add("Five", 5, Time::hm(18, 3)).unwrap();
+    waitlist.add("One", 1, Time::hm(18, 4)).unwrap();
+
+    let seated: Vec<(String, u32)> = seat_at_table(&mut waitlist, &mut floor, Time::hm(18, 10))
+      .into_iter()
+      .map(|seating| (seating.party.name, seating.table))
+      .collect();
+    assert_eq!(
+      seated,
+      [
+        (String::from("Three"), 3),
+        (String::from("Two"), 2),
+        (String::from("Five"), 1)
+      ]
+    );
+    let waiting: Vec<&str> = waitlist
+      .parties()
+      .map(|party| party.name.as_str())
+      .collect();
+    assert_eq!(waiting, ["Eight", "One"]);
+  }
+
+  #[test]
+  fn occupancy_follows_seating_and_departures() {
+    let mut floor = Floor::with_capacity(&[(1, 4), (2, 4), (3, 2)], 7).unwrap();
+    assert_eq!(floor.occupancy(), 0);
+    floor.seat(party("Ada", 4), Time::hm(18, 0)).unwrap();
+    floor.seat(party("Grace", 2), Time::hm(18, 5)).unwrap();
+    assert_eq!(floor.occupancy(), 6);
+
+    let refusal = floor.seat(party("Linus", 3), Time::hm(18, 10)).unwrap_err();
+    assert_eq!(refusal.party.name, "Linus");
+    assert_eq!(
+      refusal.reason,
+      HostingError::OverCapacity {
+        name: String::from("Linus"),
+        size: 3,
+        occupancy: 6,
+        capacity: 7
+      }
+    );
+
+    floor.clear_table(1, Time::hm(18, 45)).unwrap();
+    assert_eq!(floor.occupancy(), 2);
+    assert_eq!(floor.available_capacity(), 5);
+    floor.seat(party("Linus", 3), Time::hm(18, 50)).unwrap();
+    assert_eq!(floor.peak_occupancy(), 6);
+    assert_eq!(floor.guests_served(), 4);
+    assert_eq!(Floor::new(&[(1, 4), (2, 6)]).unwrap().capacity(), 10);
+    assert_eq!(
+      Floor::new(&[(1, 4), (2, 6), (1, 2)]),
+      Err(HostingError::DuplicateTable(1))
+    );
+  }
+
+  #[test]
+  fn the_waitlist_respects_capacity() {
+    let mut floor = Floor::with_capacity(&[(1, 6), (2, 2)], 5).unwrap();
+    let mut waitlist = Waitlist::new();
+    waitlist.add("Six", 6, Time::hm(18, 0)).unwrap();
+    waitlist.add("Two", 2, Time::hm(18, 1)).unwrap();
+
+    let seated = seat_at_table(&mut waitlist, &mut floor, Time::hm(18, 5));
+    assert_eq!(seated.len(), 1);
+    assert_eq!(seated[0].party.name, "Two");
+    assert_eq!(waitlist.position("Six"), Some(1));
+  }
+
+  #[test]
+  fn parties_can_be_seated_at_a_given_table() {
+    let mut floor = Floor::new(&[(1, 2), (2, 4)]).unwrap();
+    assert_eq!(
+      floor
+        .seat_at(party("Ada", 2), 2, Time::hm(18, 0))
+        .unwrap()
+        .table,
+      2
+    );
+
+    let refused = |floor: &mut Floor, size, number| {
+      floor
+        .seat_at(party("Grace", size), number, Time::hm(18, 5))
+        .unwrap_err()
+        .reason
+    };
+    assert_eq!(refused(&mut floor, 2, 2), HostingError::TableIsTaken(2));
+    assert_eq!(
+      refused(&mut floor, 3, 1),
+      HostingError::TableTooSmall { table: 1, size: 3 }
+    );
+    assert_eq!(refused(&mut floor, 2, 9), HostingError::NoSuchTable(9));
+  }
+
+  #[test]
+  fn turnover_tracks_how_long_tables_stay_taken() {
+    let mut floor = Floor::new(&[(1, 4)]).unwrap();
+    assert_eq!(floor.average_turnover(), DEFAULT_TURNOVER_MINUTES);
+
+    floor.seat(party("Ada", 2), Time::hm(18, 0)).unwrap();
+    assert_eq!(floor.clear_table(1, Time::hm(18, 50)).unwrap().name, "Ada");
+    floor.seat(party("Grace", 4), Time::hm(19, 0)).unwrap();
+    floor.clear_table(1, Time::hm(19, 30)).unwrap();
+    assert_eq!(floor.average_turnover(), 40);
+
+    assert_eq!(
+      floor.clear_table(1, Time::hm(20, 0)),
+      Err(HostingError::TableIsFree(1))
+    );
+    assert_eq!(
+      floor.clear_table(9, Time::hm(20, 0)),
+      Err(HostingError::NoSuchTable(9))
+    );
+  }
+
+  #[test]
+  fn estimates_waits_from_turnover() {
+    let mut floor = Floor::new(&[(1, 2), (2, 4)]).unwrap();
+    floor.seat(party("Early", 2), Time::hm(18, 0)).unwrap();
+    floor.seat(party("Earlier", 4), Time::hm(17, 50)).unwrap();
+    floor.clear_table(1, Time::hm(18, 30)).unwrap();
+    floor.seat(party("Ada", 2), Time::hm(18, 30)).unwrap();
+    // one turnover of 30 minutes so far
+
+    let mut waitlist = Waitlist::new();
+    waitlist.add("Grace", 4, Time::hm(18, 35)).unwrap();
+    waitlist.add("Linus", 2, Time::hm(18, 36)).unwrap();
+    waitlist.add("Margaret", 4, Time::hm(18, 37)).unwrap();
+    waitlist.add("Huge", 12, Time::hm(18, 38)).unwrap();
+
+    let now = Time::hm(18, 40);
+    // table 2 was taken at 17:50, so it should have been free at 18:20 already
+    assert_eq!(estimate_wait(&waitlist, &floor, "Grace", now), Ok(0));
+    // table 1 frees at 19:00
+    assert_eq!(estimate_wait(&waitlist, &floor, "Linus", now), Ok(20));
+    // after Grace is done with table 2 at 19:10
+    assert_eq!(estimate_wait(&waitlist, &floor, "Margaret", now), Ok(30));
+    assert_eq!(
+      estimate_wait(&waitlist, &floor, "Huge", now),
+      Err(HostingError::TooLarge {
+        name: String::from("Huge"),
+        size: 12
+      })
+    );
+    assert!(estimate_wait(&waitlist, &floor, "Nobody", now).is_err());
+  }
+
+  #[test]
+  fn late_service_goes_past_midnight() {
+    let mut floor = Floor::new(&[(1, 2), (2, 4)]).unwrap();
+    floor.seat(party("Owl", 2), Time::hm(23, 30)).unwrap();
+    floor.clear_table(1, Time::hm(0, 15)).unwrap();
+    assert_eq!(floor.average_turnover(), 45);
+
+    floor.seat(party("Bat", 4), Time::hm(23, 50)).unwrap();
+    floor.seat(party("Moth", 2), Time::hm(0, 5)).unwrap();
+    let mut waitlist = Waitlist::new();
+    waitlist.add("Fox", 4, Time::hm(0, 8)).unwrap();
+    waitlist.add("Cat", 2, Time::hm(0, 9)).unwrap();
+
+    let now = Time::hm(0, 10);
+    // table 2 was taken 20 minutes ago, table 1 only 5
+    assert_eq!(estimate_wait(&waitlist, &floor, "Fox", now), Ok(25));
+    assert_eq!(estimate_wait(&waitlist, &floor, "Cat", now), Ok(40));
+  }
+
+  #[test]
+  fn estimates_seat_parties_the_way_the_floor_does() {
+    let mut floor = Floor::new(&[(1, 4), (2, 2)]).unwrap();
+    let mut waitlist = Waitlist::new();
+    waitlist.add("Ada", 2, Time::hm(18, 0)).unwrap();
+    waitlist.add("Grace", 4, Time::hm(18, 1)).unwrap();
+
+    // Ada takes the two-top, which leaves the four-top for Grace straight away
+    let now = Time::hm(18, 5);
+    assert_eq!(estimate_wait(&waitlist, &floor, "Ada", now), Ok(0));
+    assert_eq!(estimate_wait(&waitlist, &floor, "Grace", now), Ok(0));
+    let seated: Vec<u32> = seat_at_table(&mut waitlist, &mut floor, now)
+      .iter()
+      .map(|seating| seating.table)
+      .collect();
+    assert_eq!(seated, [2, 1]);
+  }
+}
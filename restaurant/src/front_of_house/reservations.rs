@@ -0,0 +1,624 @@
+use std::fmt;
+
+use super::hosting::{Floor, HostingError, Party, Seating};
+use crate::clock::{Date, Time, MINUTES_PER_DAY};
+
+// Bookings for a table ahead of time. Each reservation gets a table when it's booked and holds
+// it for `BOOKING_MINUTES`, so a booking that would need a table someone else already has, or
+// would put more people inside than the floor's capacity, is turned down on the spot. A late
+// booking holds its table past midnight, into the first slots of the next day.
+
+// Reservations start on the hour or at a quarter past, half past or a quarter to.
+pub const SLOT_MINUTES: u32 = 15;
+pub const BOOKING_MINUTES: u32 = 90;
+// How late a party can be before their table is given away.
+pub const NO_SHOW_GRACE_MINUTES: u32 = 15;
+// "Tonight" starts at 17:00 and runs until 06:00 the next morning.
+pub const EVENING_STARTS_HOUR: u32 = 17;
+pub const NIGHT_ENDS_HOUR: u32 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReservationStatus {
+  Booked,
+  Arrived,
+  Cancelled,
+  NoShow,
+}
+
+impl ReservationStatus {
+  // Booked and arrived parties have a table, cancelled ones and no-shows gave theirs up.
+  pub fn holds_table(self) -> bool {
+    matches!(self, ReservationStatus::Booked | ReservationStatus::Arrived)
+  }
+}
+
+impl fmt::Display for ReservationStatus {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      ReservationStatus::Booked => "booked",
+      ReservationStatus::Arrived => "arrived",
+      ReservationStatus::Cancelled => "cancelled",
+      ReservationStatus::NoShow => "a no-show",
+    };
+    write!(f, "{name}")
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReservationError {
+  // the party itself can't be seated here, like an empty one or one bigger than every table
+  Hosting(HostingError),
+  NotOnSlot(Time),
+  // every table that fits is taken then, or the room would be over capacity
+  Overbooked { size: u32, date: Date, time: Time },
+  NoSuchReservation(u32),
+  NotBooked { id: u32, status: ReservationStatus },
+  // the party came on another day than the one they booked
+  WrongDate { id: u32, date: Date },
+}
+
+impl fmt::Display for ReservationError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ReservationError::Hosting(error) => write!(f, "{error}"),
+      ReservationError::NotOnSlot(time) => write!(
+        f,
+        "reservations are every {SLOT_MINUTES} minutes, {time} isn't one of them"
+      ),
+      ReservationError::Overbooked { size, date, time } => {
+        write!(
+          f,
+          "there's no room for a party of {size} on {date} at {time}"
+        )
+      }
+      ReservationError::NoSuchReservation(id) => write!(f, "there's no reservation {id}"),
+      ReservationError::NotBooked { id, status } => {
+        write!(f, "reservation {id} is already {status}")
+      }
+      ReservationError::WrongDate { id, date } => write!(f, "reservation {id} is for {date}"),
+    }
+  }
+}
+
+impl std::error::Error for ReservationError {}
+
+impl From<HostingError> for ReservationError {
+  fn from(error: HostingError) -> ReservationError {
+    ReservationError::Hosting(error)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reservation {
+  pub id: u32,
+  pub name: String,
+  pub size: u32,
+  pub date: Date,
+  pub time: Time,
+  pub table: u32,
+  pub status: ReservationStatus,
+}
+
+impl Reservation {
+  // Past 24:00 for a booking that runs into the next day, see `Time::plus_minutes`.
+  pub fn ends(&self) -> Time {
+    self.time.plus_minutes(BOOKING_MINUTES)
+  }
+
+  fn overlaps(&self, date: Date, time: Time) -> bool {
+    if !self.status.holds_table() {
+      return false;
+    }
+    // both starts in minutes from midnight on the earlier of the two days
+    let (mine, theirs) = if date == self.date {
+      (self.time.minutes(), time.minutes())
+    } else if date == self.date.next_day() {
+      (self.time.minutes(), time.minutes() + MINUTES_PER_DAY)
+    } else if self.date == date.next_day() {
+      (self.time.minutes() + MINUTES_PER_DAY, time.minutes())
+    } else {
+      return false;
+    };
+    mine < theirs + BOOKING_MINUTES && theirs < mine + BOOKING_MINUTES
+  }
+
+  // How far past the booked time `now` on `date` is, negative when it's still before. Works
+  // across midnight either way, so a 23:30 booking is 40 minutes late at 00:10 the next day.
+  // `None` when the booking is further away than the day before or after.
+  fn minutes_late(&self, date: Date, now: Time) -> Option<i64> {
+    let booked = i64::from(self.time.minutes());
+    let now = i64::from(now.minutes());
+    let day = i64::from(MINUTES_PER_DAY);
+    if date == self.date {
+      Some(now - booked)
+    } else if date == self.date.next_day() {
+      Some(now + day - booked)
+    } else if self.date == date.next_day() {
+      Some(now - day - booked)
+    } else {
+      None
+    }
+  }
+}
+
+impl fmt::Display for Reservation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} {}, party of {} at table {} ({})",
+      self.time, self.name, self.size, self.table, self.status
+    )
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReservationBook {
+  reservations: Vec<Reservation>,
+  next_id: u32,
+}
+
+impl ReservationBook {
+  pub fn new() -> ReservationBook {
+    ReservationBook::default()
+  }
+
+  // Books the smallest table on `floor` that fits the party and is free for the whole booking.
+  pub fn book(
+    &mut self,
+    floor: &Floor,
+    name: &str,
+    size: u32,
+    date: Date,
+    time: Time,
+  ) -> Result<&Reservation, ReservationError> {
+    if size == 0 {
+      return Err(HostingError::EmptyParty.into());
+    }
+    if size > floor.largest_table() {
+      return Err(
+        HostingError::TooLarge {
+          name: String::from(name),
+          size,
+        }
+        .into(),
+      );
+    }
+    if !time.minutes().is_multiple_of(SLOT_MINUTES) {
+      return Err(ReservationError::NotOnSlot(time));
+    }
+
+    let overlapping: Vec<&Reservation> = self
+      .reservations
+      .iter()
+      .filter(|reservation| reservation.overlaps(date, time))
+      .collect();
+    let overbooked = ReservationError::Overbooked { size, date, time };
+    // counting every overlapping party as inside at once, which is the worst case
+    let expected: u32 = overlapping.iter().map(|reservation| reservation.size).sum();
+    if expected + size > floor.capacity() {
+      return Err(overbooked);
+    }
+    let table = floor
+      .tables()
+      .iter()
+      .filter(|table| table.seats >= size)
+      .filter(|table| !overlapping.iter().any(|taken| taken.table == table.number))
+      .min_by_key(|table| (table.seats, table.number))
+      .ok_or(overbooked)?
+      .number;
+
+    self.next_id += 1;
+    self.reservations.push(Reservation {
+      id: self.next_id,
+      name: String::from(name),
+      size,
+      date,
+      time,
+      table,
+      status: ReservationStatus::Booked,
+    });
+    Ok(self.reservations.last().expect("it was just pushed"))
+  }
+
+  pub fn get(&self, id: u32) -> Option<&Reservation> {
+    self
+      .reservations
+      .iter()
+      .find(|reservation| reservation.id == id)
+  }
+
+  // Only a reservation that's still booked can change, to any other status.
+  fn change(&mut self, id: u32, to: ReservationStatus) -> Result<&Reservation, ReservationError> {
+    let reservation = self
+      .reservations
+      .iter_mut()
+      .find(|reservation| reservation.id == id)
+      .ok_or(ReservationError::NoSuchReservation(id))?;
+    if reservation.status != ReservationStatus::Booked {
+      return Err(ReservationError::NotBooked {
+        id,
+        status: reservation.status,
+      });
+    }
+    reservation.status = to;
+    Ok(reservation)
+  }
+
+  // The table goes back to being bookable.
+  pub fn cancel(&mut self, id: u32) -> Result<(), ReservationError> {
+    self.change(id, ReservationStatus::Cancelled).map(|_| ())
+  }
+
+  pub fn mark_no_show(&mut self, id: u32) -> Result<(), ReservationError> {
+    self.change(id, ReservationStatus::NoShow).map(|_| ())
+  }
+
+  // Every reservation that's more than the grace period late by `now` on `date` becomes a
+  // no-show, last night's ones too when it's already past midnight. Returns their ids.
+  pub fn mark_no_shows(&mut self, date: Date, now: Time) -> Vec<u32> {
+    let grace = i64::from(NO_SHOW_GRACE_MINUTES);
+    let late: Vec<u32> = self
+      .reservations
+      .iter()
+      .filter(|reservation| {
+        reservation.status == ReservationStatus::Booked
+          && reservation
+            .minutes_late(date, now)
+            .is_some_and(|late| late > grace)
+      })
+      .map(|reservation| reservation.id)
+      .collect();
+    for &id in &late {
+      self
+        .mark_no_show(id)
+        .expect("only booked reservations were picked");
+    }
+    late
+  }
+
+  // The party has arrived, as a `Party` for the floor.
+  pub fn check_in(&mut self, id: u32, now: Time) -> Result<Party, ReservationError> {
+    let reservation = self.change(id, ReservationStatus::Arrived)?;
+    Ok(Party {
+      name: reservation.name.clone(),
+      size: reservation.size,
+      arrived: now,
+    })
+  }
+
+  // Every reservation on `date`, cancelled ones and no-shows too, by time and then table.
+  pub fn on(&self, date: Date) -> Vec<&Reservation> {
+    let mut day: Vec<&Reservation> = self
+      .reservations
+      .iter()
+      .filter(|reservation| reservation.date == date)
+      .collect();
+    day.sort_by_key(|reservation| (reservation.time, reservation.table));
+    day
+  }
+
+  // The evening's parties that are still expected or already here, with the ones booked past
+  // midnight at the end.
+  pub fn tonight(&self, date: Date) -> Vec<&Reservation> {
+    let after_midnight = self
+      .on(date.next_day())
+      .into_iter()
+      .filter(|reservation| reservation.time.hour() < NIGHT_ENDS_HOUR);
+    self
+      .on(date)
+      .into_iter()
+      .filter(|reservation| reservation.time.hour() >= EVENING_STARTS_HOUR)
+      .chain(after_midnight)
+      .filter(|reservation| reservation.status.holds_table())
+      .collect()
+  }
+}
+
+// Checks the party in and seats them at the table they booked. If that table is still taken,
+// say the party before them is running late, or the room is full, they stay booked and the
+// error says why, so the host can try again once it clears or seat them somewhere else.
+// `date` is the day it is at `now`: the booking has to be for that day, or be less than a
+// booking away on the other side of midnight.
+pub fn seat_reservation(
+  book: &mut ReservationBook,
+  floor: &mut Floor,
+  id: u32,
+  date: Date,
+  now: Time,
+) -> Result<Seating, ReservationError> {
+  let reservation = book
+    .get(id)
+    .ok_or(ReservationError::NoSuchReservation(id))?;
+  if reservation.status != ReservationStatus::Booked {
+    return Err(ReservationError::NotBooked {
+      id,
+      status: reservation.status,
+    });
+  }
+  let around_midnight = reservation
+    .minutes_late(date, now)
+    .is_some_and(|late| late.unsigned_abs() < u64::from(BOOKING_MINUTES));
+  if reservation.date != date && !around_midnight {
+    return Err(ReservationError::WrongDate {
+      id,
+      date: reservation.date,
+    });
+  }
+  let party = Party {
+    name: reservation.name.clone(),
+    size: reservation.size,
+    arrived: now,
+  };
+  let seating = floor
+    .seat_at(party, reservation.table, now)
+    .map_err(|refusal| ReservationError::Hosting(refusal.reason))?;
+  book.check_in(id, now)?;
+  Ok(seating)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn floor() -> Floor {
+    Floor::with_capacity(&[(1, 2), (2, 4), (3, 4)], 8).unwrap()
+  }
+
+  fn friday() -> Date {
+    Date::ymd(2024, 7, 19)
+  }
+
+  #[test]
+  fn books_the_smallest_free_table() {
+    let floor = floor();
+    let mut book = ReservationBook::new();
+    let seven = Time::hm(19, 0);
+    assert_eq!(
+      book.book(&floor, "Ada", 2, friday(), seven).unwrap().table,
+      1
+    );
+    assert_eq!(
+      book
+        .book(&floor, "Grace", 2, friday(), seven)
+        .unwrap()
+        .table,
+      2
+    );
+    // Ada's table is free again by half past eight
+    let later = book
+      .book(&floor, "Linus", 2, friday(), Time::hm(20, 30))
+      .unwrap();
+    assert_eq!((later.id, later.table), (3, 1));
+
+    assert_eq!(
+      book.book(&floor, "Ken", 5, friday(), seven).unwrap_err(),
+      ReservationError::Hosting(HostingError::TooLarge {
+        name: String::from("Ken"),
+        size: 5
+      })
+    );
+    assert_eq!(
+      book
+        .book(&floor, "Ken", 2, friday(), Time::hm(19, 10))
+        .unwrap_err(),
+      ReservationError::NotOnSlot(Time::hm(19, 10))
+    );
+  }
+
+  #[test]
+  fn refuses_to_overbook() {
+    let floor = floor();
+    let mut book = ReservationBook::new();
+    let seven = Time::hm(19, 0);
+    book.book(&floor, "Ada", 4, friday(), seven).unwrap();
+    book.book(&floor, "Grace", 3, friday(), seven).unwrap();
+
+    let overbooked = ReservationError::Overbooked {
+      size: 2,
+      date: friday(),
+      time: Time::hm(19, 30),
+    };
+    // table 1 is free, but that would be ten people with a capacity of eight
+    assert_eq!(
+      book
+        .book(&floor, "Linus", 2, friday(), Time::hm(19, 30))
+        .unwrap_err(),
+      overbooked
+    );
+    // a different night is fine
+    assert!(book
+      .book(&floor, "Linus", 2, Date::ymd(2024, 7, 20), Time::hm(19, 30))
+      .is_ok());
+
+    // cancelling frees up the room
+    book.cancel(2).unwrap();
+    assert!(book
+      .book(&floor, "Linus", 2, friday(), Time::hm(19, 30))
+      .is_ok());
+    assert_eq!(
+      book.cancel(2),
+      Err(ReservationError::NotBooked {
+        id: 2,
+        status: ReservationStatus::Cancelled
+      })
+    );
+  }
+
+  #[test]
+  fn late_bookings_hold_their_table_past_midnight() {
+    let floor = floor();
+    let mut book = ReservationBook::new();
+    let saturday = friday().next_day();
+    book
+      .book(&floor, "Ada", 2, friday(), Time::hm(23, 30))
+      .unwrap();
+
+    // Ada has table 1 until one in the morning
+    let early = book
+      .book(&floor, "Grace", 2, saturday, Time::hm(0, 30))
+      .unwrap();
+    assert_eq!(early.table, 2);
+    let later = book
+      .book(&floor, "Linus", 2, saturday, Time::hm(1, 0))
+      .unwrap();
+    assert_eq!(later.table, 1);
+  }
+
+  #[test]
+  fn late_bookings_are_seated_and_missed_after_midnight() {
+    let mut floor = floor();
+    let mut book = ReservationBook::new();
+    let saturday = friday().next_day();
+    book
+      .book(&floor, "Grace", 4, friday(), Time::hm(23, 30))
+      .unwrap();
+    book
+      .book(&floor, "Ada", 2, friday(), Time::hm(23, 45))
+      .unwrap();
+    book
+      .book(&floor, "Linus", 2, saturday, Time::hm(0, 15))
+      .unwrap();
+
+    // Grace is late, but not yet by more than the grace period
+    assert_eq!(
+      book.mark_no_shows(friday(), Time::hm(23, 40)),
+      Vec::<u32>::new()
+    );
+    // Linus turns up a little early, before midnight
+    let seating = seat_reservation(&mut book, &mut floor, 3, friday(), Time::hm(23, 55)).unwrap();
+    assert_eq!(seating.table, 3);
+    // Ada comes in right at midnight, a quarter of an hour late
+    let seating = seat_reservation(&mut book, &mut floor, 2, saturday, Time::hm(0, 0)).unwrap();
+    assert_eq!(seating.table, 1);
+    // and Grace is thirty-five minutes late at five past twelve
+    assert_eq!(book.mark_no_shows(saturday, Time::hm(0, 5)), [1]);
+
+    // a whole day late is the wrong day
+    book
+      .book(&floor, "Ken", 2, friday(), Time::hm(20, 0))
+      .unwrap();
+    assert_eq!(
+      seat_reservation(&mut book, &mut floor, 4, saturday, Time::hm(20, 0)).unwrap_err(),
+      ReservationError::WrongDate {
+        id: 4,
+        date: friday()
+      }
+    );
+  }
+
+  #[test]
+  fn parties_sit_at_the_table_they_booked() {
+    let mut floor = floor();
+    let mut book = ReservationBook::new();
+    book
+      .book(&floor, "Ada", 2, friday(), Time::hm(18, 0))
+      .unwrap();
+    book
+      .book(&floor, "Grace", 2, friday(), Time::hm(18, 30))
+      .unwrap();
+    book.cancel(1).unwrap();
+
+    // table 1 would fit Grace best now, but she was given table 2
+    let seating = seat_reservation(&mut book, &mut floor, 2, friday(), Time::hm(18, 30)).unwrap();
+    assert_eq!(seating.table, 2);
+
+    book
+      .book(&floor, "Linus", 2, friday(), Time::hm(20, 0))
+      .unwrap();
+    floor
+      .seat_at(
+        Party {
+          name: String::from("Walk-in"),
+          size: 2,
+          arrived: Time::hm(19, 30),
+        },
+        1,
+        Time::hm(19, 30),
+      )
+      .unwrap();
+    assert_eq!(
+      seat_reservation(&mut book, &mut floor, 3, friday(), Time::hm(20, 0)).unwrap_err(),
+      ReservationError::Hosting(HostingError::TableIsTaken(1))
+    );
+    assert_eq!(book.get(3).unwrap().status, ReservationStatus::Booked);
+  }
+
+  #[test]
+  fn lists_tonights_reservations() {
+    let floor = floor();
+    let mut book = ReservationBook::new();
+    book
+      .book(&floor, "Linus", 2, friday(), Time::hm(20, 0))
+      .unwrap();
+    book
+      .book(&floor, "Ada", 2, friday(), Time::hm(18, 0))
+      .unwrap();
+    book
+      .book(&floor, "Lunch", 4, friday(), Time::hm(12, 30))
+      .unwrap();
+    book
+      .book(&floor, "Grace", 4, friday(), Time::hm(18, 0))
+      .unwrap();
+    book
+      .book(&floor, "Ken", 4, friday(), Time::hm(21, 0))
+      .unwrap();
+    book.cancel(5).unwrap();
+    let saturday = friday().next_day();
+    book
+      .book(&floor, "Owl", 2, saturday, Time::hm(0, 30))
+      .unwrap();
+    book
+      .book(&floor, "Brunch", 2, saturday, Time::hm(11, 0))
+      .unwrap();
+
+    let tonight: Vec<String> = book
+      .tonight(friday())
+      .iter()
+      .map(|reservation| reservation.to_string())
+      .collect();
+    assert_eq!(
+      tonight,
+      [
+        "18:00 Ada, party of 2 at table 1 (booked)",
+        "18:00 Grace, party of 4 at table 2 (booked)",
+        "20:00 Linus, party of 2 at table 1 (booked)",
+        "00:30 Owl, party of 2 at table 1 (booked)",
+      ]
+    );
+    assert_eq!(book.on(friday()).len(), 5);
+  }
+
+  #[test]
+  fn late_parties_become_no_shows() {
+    let mut floor = floor();
+    let mut book = ReservationBook::new();
+    book
+      .book(&floor, "Ada", 2, friday(), Time::hm(18, 0))
+      .unwrap();
+    book
+      .book(&floor, "Grace", 4, friday(), Time::hm(18, 0))
+      .unwrap();
+    book
+      .book(&floor, "Linus", 2, friday(), Time::hm(19, 0))
+      .unwrap();
+
+    let seating = seat_reservation(&mut book, &mut floor, 1, friday(), Time::hm(18, 5)).unwrap();
+    assert_eq!((seating.party.name.as_str(), seating.table), ("Ada", 1));
+    assert_eq!(
+      book.mark_no_shows(friday(), Time::hm(18, 15)),
+      Vec::<u32>::new()
+    );
+    assert_eq!(book.mark_no_shows(friday(), Time::hm(18, 16)), [2]);
+    assert_eq!(book.get(2).unwrap().status, ReservationStatus::NoShow);
+    assert_eq!(book.get(1).unwrap().status, ReservationStatus::Arrived);
+
+    book.mark_no_show(3).unwrap();
+    assert_eq!(
+      seat_reservation(&mut book, &mut floor, 3, friday(), Time::hm(19, 30)).unwrap_err(),
+      ReservationError::NotBooked {
+        id: 3,
+        status: ReservationStatus::NoShow
+      }
+    );
+  }
+}
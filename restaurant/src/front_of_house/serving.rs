@@ -0,0 +1,271 @@
+use std::fmt;
+
+// An order goes through these states in this order, it can't skip one or go back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OrderState {
+  // the waiter is still writing it down, items can be added and removed
+  Taken,
+  InKitchen,
+  Served,
+  Paid,
+}
+
+impl OrderState {
+  // The state that comes after this one, `None` once the order is paid.
+  pub fn next(self) -> Option<OrderState> {
+    match self {
+      OrderState::Taken => Some(OrderState::InKitchen),
+      OrderState::InKitchen => Some(OrderState::Served),
+      OrderState::Served => Some(OrderState::Paid),
+      OrderState::Paid => None,
+    }
+  }
+}
+
+impl fmt::Display for OrderState {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      OrderState::Taken => "taken",
+      OrderState::InKitchen => "in the kitchen",
+      OrderState::Served => "served",
+      OrderState::Paid => "paid",
+    };
+    write!(f, "{name}")
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderError {
+  InvalidTransition { from: OrderState, to: OrderState },
+  // only orders that are still being taken can change
+  NotEditable(OrderState),
+  EmptyOrder,
+  ZeroQuantity(String),
+  NoSuchItem(String),
+}
+
+impl fmt::Display for OrderError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      OrderError::InvalidTransition { from, to } => {
+        write!(f, "an order that is {from} can't become {to}")
+      }
+      OrderError::NotEditable(state) => {
+        write!(f, "the order is already {state}, it can't be changed")
+      }
+      OrderError::EmptyOrder => write!(f, "the order has no items"),
+      OrderError::ZeroQuantity(name) => write!(f, "can't order zero of {name}"),
+      OrderError::NoSuchItem(name) => write!(f, "there's no {name} on the order"),
+    }
+  }
+}
+
+impl std::error::Error for OrderError {}
+
+// One line on the order: what, how many and what one of them costs, in cents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineItem {
+  pub name: String,
+  pub quantity: u32,
+  pub unit_price_cents: u64,
+}
+
+impl LineItem {
+  pub fn new(name: &str, quantity: u32, unit_price_cents: u64) -> LineItem {
+    LineItem {
+      name: String::from(name),
+      quantity,
+      unit_price_cents,
+    }
+  }
+
+  pub fn total_cents(&self) -> u64 {
+    self.unit_price_cents * u64::from(self.quantity)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Order {
+  id: u32,
+  table: u32,
+  items: Vec<LineItem>,
+  state: OrderState,
+}
+
+impl Order {
+  pub fn new(id: u32, table: u32) -> Order {
+    Order {
+      id,
+      table,
+      items: Vec::new(),
+      state: OrderState::Taken,
+    }
+  }
+
+  pub fn id(&self) -> u32 {
+    self.id
+  }
+
+  pub fn table(&self) -> u32 {
+    self.table
+  }
+
+  pub fn items(&self) -> &[LineItem] {
+    &self.items
+  }
+
+  pub fn state(&self) -> OrderState {
+    self.state
+  }
+
+  pub fn total_cents(&self) -> u64 {
+    self.items.iter().map(LineItem::total_cents).sum()
+  }
+
+  // Ordering something that's already on the order adds to its quantity.
+  pub fn add_item(&mut self, item: LineItem) -> Result<(), OrderError> {
+    self.check_editable()?;
+    if item.quantity == 0 {
+      return Err(OrderError::ZeroQuantity(item.name));
+    }
+    match self.items.iter_mut().find(|existing| {
+      existing.name == item.name && existing.unit_price_cents == item.unit_price_cents
+    }) {
+      Some(existing) => existing.quantity += item.quantity,
+      None => self.items.push(item),
+    }
+    Ok(())
+  }
+
+  pub fn remove_item(&mut self, name: &str) -> Result<LineItem, OrderError> {
+    self.check_editable()?;
+    let index = self
+      .items
+      .iter()
+      .position(|item| item.name == name)
+      .ok_or_else(|| OrderError::NoSuchItem(String::from(name)))?;
+    Ok(self.items.remove(index))
+  }
+
+  fn check_editable(&self) -> Result<(), OrderError> {
+    match self.state {
+      OrderState::Taken => Ok(()),
+      state => Err(OrderError::NotEditable(state)),
+    }
+  }
+
+  // Moves the order on to `to`, which has to be the state right after the current one.
+  pub fn advance_to(&mut self, to: OrderState) -> Result<(), OrderError> {
+    if self.state.next() != Some(to) {
+      return Err(OrderError::InvalidTransition {
+        from: self.state,
+        to,
+      });
+    }
+    if to == OrderState::InKitchen && self.items.is_empty() {
+      return Err(OrderError::EmptyOrder);
+    }
+    self.state = to;
+    Ok(())
+  }
+
+  pub fn send_to_kitchen(&mut self) -> Result<(), OrderError> {
+    self.advance_to(OrderState::InKitchen)
+  }
+
+  pub fn serve(&mut self) -> Result<(), OrderError> {
+    self.advance_to(OrderState::Served)
+  }
+
+  pub fn pay(&mut self) -> Result<(), OrderError> {
+    self.advance_to(OrderState::Paid)
+  }
+}
+
+// Writes the items down and sends the order to the kitchen.
+pub fn take_order(id: u32, table: u32, items: Vec<LineItem>) -> Result<Order, OrderError> {
+  let mut order = Order::new(id, table);
+  for item in items {
+    order.add_item(item)?;
+  }
+  order.send_to_kitchen()?;
+  Ok(order)
+}
+
+pub fn serve_order(order: &mut Order) -> Result<(), OrderError> {
+  order.serve()
+}
+
+pub fn take_payment(order: &mut Order) -> Result<(), OrderError> {
+  order.pay()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn an_order_goes_from_taken_to_paid() {
+    let mut order = take_order(
+      1,
+      4,
+      vec![
+        LineItem::new("Soup", 2, 550),
+        LineItem::new("Salad", 1, 800),
+        LineItem::new("Soup", 1, 550),
+      ],
+    )
+    .unwrap();
+    assert_eq!(order.state(), OrderState::InKitchen);
+    assert_eq!(order.items().len(), 2);
+    assert_eq!(order.total_cents(), 3 * 550 + 800);
+
+    serve_order(&mut order).unwrap();
+    take_payment(&mut order).unwrap();
+    assert_eq!(order.state(), OrderState::Paid);
+    assert_eq!(order.state().next(), None);
+  }
+
+  #[test]
+  fn transitions_cannot_skip_or_go_back() {
+    let mut order = Order::new(2, 1);
+    assert_eq!(order.send_to_kitchen(), Err(OrderError::EmptyOrder));
+    assert_eq!(
+      order.pay(),
+      Err(OrderError::InvalidTransition {
+        from: OrderState::Taken,
+        to: OrderState::Paid
+      })
+    );
+
+    order.add_item(LineItem::new("Salad", 1, 800)).unwrap();
+    order.send_to_kitchen().unwrap();
+    assert_eq!(
+      order.advance_to(OrderState::Taken),
+      Err(OrderError::InvalidTransition {
+        from: OrderState::InKitchen,
+        to: OrderState::Taken
+      })
+    );
+  }
+
+  #[test]
+  fn only_orders_being_taken_can_change() {
+    let mut order = Order::new(3, 2);
+    assert_eq!(
+      order.add_item(LineItem::new("Soup", 0, 550)),
+      Err(OrderError::ZeroQuantity(String::from("Soup")))
+    );
+    order.add_item(LineItem::new("Soup", 1, 550)).unwrap();
+    assert_eq!(
+      order.remove_item("Salad"),
+      Err(OrderError::NoSuchItem(String::from("Salad")))
+    );
+
+    order.send_to_kitchen().unwrap();
+    assert_eq!(
+      order.remove_item("Soup"),
+      Err(OrderError::NotEditable(OrderState::InKitchen))
+    );
+  }
+}
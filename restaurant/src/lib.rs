@@ -1,67 +1,61 @@
+mod back_of_house;
+pub mod clock;
 mod front_of_house;
 // use crate::front_of_house::hosting;
-use std::io::Result as IoResult;
 
 // re-exporting names with pub use
-pub use crate::front_of_house::{hosting, total_customer_size};
+pub use crate::back_of_house::{
+    cook_order, deliver_when_ready, fix_incorrect_order, kitchen, menu,
+};
+pub use crate::front_of_house::{billing, hosting, reservations, serving, total_customer_size};
 // use crate::front_of_house::*;
 
 // the front_of_house isn't public, but because the eat_at_restaurant function defined in the same module as front_of_house, we can refer to front_of_house
-pub fn eat_at_restaurant() {
+// (examples/dinner.rs goes through a whole evening, from the reservation book to the check)
+pub fn eat_at_restaurant() -> Result<(), hosting::HostingError> {
+    let mut waitlist = hosting::Waitlist::new();
+    let arrived = clock::Time::hm(18, 30);
     // absolute path
-    crate::front_of_house::hosting::add_to_waitlist();
+    crate::front_of_house::hosting::add_to_waitlist(&mut waitlist, "Ada", 2, arrived)?;
     // relative path
-    front_of_house::hosting::add_to_waitlist();
+    front_of_house::hosting::add_to_waitlist(&mut waitlist, "Grace", 4, arrived)?;
     // after `use` keyword, we can just use
-    hosting::add_to_waitlist();
+    hosting::add_to_waitlist(&mut waitlist, "Linus", 3, arrived)?;
+    let mut floor = hosting::Floor::with_capacity(&[(1, 2), (2, 4), (3, 4)], 8)?;
+    hosting::seat_at_table(&mut waitlist, &mut floor, arrived);
 
     // Order a breakfast in the summer with Rye toast
     let mut meal = back_of_house::Breakfast::summer("Rye");
     meal.toast = String::from("Wheat");
     println!("I'd like {} toast please", meal.toast);
+    // or let the kitchen pick whatever fruit is in season today
+    let other_meal = back_of_house::Breakfast::on(clock::Date::ymd(2024, 10, 3), "Sourdough");
+    println!("here's your {}", meal.plate());
+    println!("and your {}", other_meal.plate());
 
     // The next line won't compile if we uncomment it; we're not allowed
     // to see or modify the seasonal fruit that comes with the meal
     // meal.seasonal_fruit = String::from("blueberries");
 
+    let menu = menu::Menu::standard();
     let order1 = back_of_house::Appetizer::Soup;
     let order2 = back_of_house::Appetizer::Salad;
-    println!("currently {} people are dining", total_customer_size());
+    for appetizer in [order1, order2] {
+        if let Some(item) = appetizer.menu_item(&menu) {
+            println!("{item}");
+        }
+    }
+    println!("currently {} people are dining", total_customer_size(&floor));
+    Ok(())
 }
 
 // we can also construct relative paths that begin in the parent module by using super at the start of the path. this is like starting a filesystem path with the `..` syntax.
 // for example;
 
-fn deliver_order() {}
-
-mod back_of_house {
-    fn fix_incorrect_order() {
-        cook_order();
-        super::deliver_order();
-    }
-    fn cook_order() {}
-    // Making structs and enums public
-    // We can also use pub to designate structs and enums as public, but there are a few extra details. If we use pub before a struct definition, we make the struct public,
-    // but the struct's fields will still be private.
-    pub struct Breakfast {
-        pub toast: String,
-        seasonal_fruit: String,
-    }
-    impl Breakfast {
-        // Becase back_of_house::Breakfast has a private field, the struct needs to provide a public associated function that constructs an instance of Breakfast (summer)
-        // if Breakfast didn't have such a function, we couldn't create an instance of Breakfast in eat_at_restaurant because we couldn't set the value of the private
-        // seasonal_fruit field
-        pub fn summer(toast: &str) -> Breakfast {
-            Breakfast {
-                toast: String::from(toast),
-                seasonal_fruit: String::from("peaches"),
-            }
-        }
-    }
-
-    // If we make an enum public, all of its variants are then public
-    pub enum Appetizer {
-        Soup,
-        Salad,
+// A remake goes back out to a table that was already served.
+fn deliver_order(order: &mut serving::Order) -> Result<(), serving::OrderError> {
+    match order.state() {
+        serving::OrderState::Served => Ok(()),
+        _ => serving::serve_order(order),
     }
 }